@@ -0,0 +1,165 @@
+#[macro_use]
+extern crate bencher;
+
+use bencher::Bencher;
+
+extern crate pfds;
+
+use pfds::heap::*;
+
+fn bench_pf_heap_insert<Heap: PfHeap<i32>>(b: &mut Bencher) {
+    b.iter(|| {
+        let mut h = Heap::new();
+        for i in 0..1000 {
+            h = h.insert((i * 7919) % 1000);
+        }
+    })
+}
+
+fn bench_pf_heap_insert_sorted<Heap: PfHeap<i32>>(b: &mut Bencher) {
+    b.iter(|| {
+        let mut h = Heap::new();
+        for i in 0..1000 {
+            h = h.insert(i);
+        }
+    })
+}
+
+fn bench_pf_heap_delete_min<Heap: PfHeap<i32>>(b: &mut Bencher) {
+    let mut h_full = Heap::new();
+    for i in 0..1000 {
+        h_full = h_full.insert((i * 7919) % 1000);
+    }
+    b.iter(|| {
+        let mut h = h_full.clone();
+        while let Ok(nh) = h.delete_min() {
+            h = nh;
+        }
+    })
+}
+
+fn bench_pf_leftist_heap_insert(b: &mut Bencher) {
+    bench_pf_heap_insert::<PfLeftistHeap<i32>>(b);
+}
+
+fn bench_pf_leftist_heap_insert_sorted(b: &mut Bencher) {
+    bench_pf_heap_insert_sorted::<PfLeftistHeap<i32>>(b);
+}
+
+fn bench_pf_leftist_heap_delete_min(b: &mut Bencher) {
+    bench_pf_heap_delete_min::<PfLeftistHeap<i32>>(b);
+}
+
+fn bench_pf_binomial_heap_insert(b: &mut Bencher) {
+    bench_pf_heap_insert::<PfBinomialHeap<i32>>(b);
+}
+
+fn bench_pf_binomial_heap_insert_sorted(b: &mut Bencher) {
+    bench_pf_heap_insert_sorted::<PfBinomialHeap<i32>>(b);
+}
+
+fn bench_pf_binomial_heap_delete_min(b: &mut Bencher) {
+    bench_pf_heap_delete_min::<PfBinomialHeap<i32>>(b);
+}
+
+fn bench_pf_splay_heap_insert(b: &mut Bencher) {
+    bench_pf_heap_insert::<PfSplayHeap<i32>>(b);
+}
+
+fn bench_pf_splay_heap_insert_sorted(b: &mut Bencher) {
+    bench_pf_heap_insert_sorted::<PfSplayHeap<i32>>(b);
+}
+
+fn bench_pf_splay_heap_delete_min(b: &mut Bencher) {
+    bench_pf_heap_delete_min::<PfSplayHeap<i32>>(b);
+}
+
+fn bench_pf_pairing_heap_insert(b: &mut Bencher) {
+    bench_pf_heap_insert::<PfPairingHeap<i32>>(b);
+}
+
+fn bench_pf_pairing_heap_insert_sorted(b: &mut Bencher) {
+    bench_pf_heap_insert_sorted::<PfPairingHeap<i32>>(b);
+}
+
+fn bench_pf_pairing_heap_delete_min(b: &mut Bencher) {
+    bench_pf_heap_delete_min::<PfPairingHeap<i32>>(b);
+}
+
+fn bench_pf_lazy_pairing_heap_insert(b: &mut Bencher) {
+    bench_pf_heap_insert::<PfLazyPairingHeap<i32>>(b);
+}
+
+fn bench_pf_lazy_pairing_heap_insert_sorted(b: &mut Bencher) {
+    bench_pf_heap_insert_sorted::<PfLazyPairingHeap<i32>>(b);
+}
+
+fn bench_pf_lazy_pairing_heap_delete_min(b: &mut Bencher) {
+    bench_pf_heap_delete_min::<PfLazyPairingHeap<i32>>(b);
+}
+
+fn bench_pf_scheduled_binomial_heap_insert(b: &mut Bencher) {
+    bench_pf_heap_insert::<PfScheduledBinomialHeap<i32>>(b);
+}
+
+fn bench_pf_scheduled_binomial_heap_insert_sorted(b: &mut Bencher) {
+    bench_pf_heap_insert_sorted::<PfScheduledBinomialHeap<i32>>(b);
+}
+
+fn bench_pf_scheduled_binomial_heap_delete_min(b: &mut Bencher) {
+    bench_pf_heap_delete_min::<PfScheduledBinomialHeap<i32>>(b);
+}
+
+fn bench_pf_skew_binomial_heap_insert(b: &mut Bencher) {
+    bench_pf_heap_insert::<PfSkewBinomialHeap<i32>>(b);
+}
+
+fn bench_pf_skew_binomial_heap_insert_sorted(b: &mut Bencher) {
+    bench_pf_heap_insert_sorted::<PfSkewBinomialHeap<i32>>(b);
+}
+
+fn bench_pf_skew_binomial_heap_delete_min(b: &mut Bencher) {
+    bench_pf_heap_delete_min::<PfSkewBinomialHeap<i32>>(b);
+}
+
+fn bench_pf_bootstrapped_heap_insert(b: &mut Bencher) {
+    bench_pf_heap_insert::<PfBootstrappedHeap<PfSkewBinomialHeap<i32>>>(b);
+}
+
+fn bench_pf_bootstrapped_heap_insert_sorted(b: &mut Bencher) {
+    bench_pf_heap_insert_sorted::<
+        PfBootstrappedHeap<PfSkewBinomialHeap<i32>>>(b);
+}
+
+fn bench_pf_bootstrapped_heap_delete_min(b: &mut Bencher) {
+    bench_pf_heap_delete_min::<
+        PfBootstrappedHeap<PfSkewBinomialHeap<i32>>>(b);
+}
+
+benchmark_group!(benches,
+                 bench_pf_bootstrapped_heap_delete_min,
+                 bench_pf_bootstrapped_heap_insert,
+                 bench_pf_bootstrapped_heap_insert_sorted,
+                 bench_pf_binomial_heap_delete_min,
+                 bench_pf_binomial_heap_insert,
+                 bench_pf_binomial_heap_insert_sorted,
+                 bench_pf_lazy_pairing_heap_delete_min,
+                 bench_pf_lazy_pairing_heap_insert,
+                 bench_pf_lazy_pairing_heap_insert_sorted,
+                 bench_pf_leftist_heap_delete_min,
+                 bench_pf_leftist_heap_insert,
+                 bench_pf_leftist_heap_insert_sorted,
+                 bench_pf_pairing_heap_delete_min,
+                 bench_pf_pairing_heap_insert,
+                 bench_pf_pairing_heap_insert_sorted,
+                 bench_pf_scheduled_binomial_heap_delete_min,
+                 bench_pf_scheduled_binomial_heap_insert,
+                 bench_pf_scheduled_binomial_heap_insert_sorted,
+                 bench_pf_skew_binomial_heap_delete_min,
+                 bench_pf_skew_binomial_heap_insert,
+                 bench_pf_skew_binomial_heap_insert_sorted,
+                 bench_pf_splay_heap_delete_min,
+                 bench_pf_splay_heap_insert,
+                 bench_pf_splay_heap_insert_sorted
+);
+benchmark_main!(benches);
@@ -5,6 +5,7 @@ use bencher::Bencher;
 
 extern crate pfds;
 
+use pfds::catenable::*;
 use pfds::queue::*;
 
 fn bench_pf_queue_snoc<Queue: PfQueue<i32>>(b: &mut Bencher) {
@@ -23,11 +24,8 @@ fn bench_pf_queue_tail<Queue: PfQueue<i32>>(b: &mut Bencher) {
     }
     b.iter(|| {
         let mut q = q_full.clone();
-        loop {
-            match q.tail() {
-                Ok(nq) => q = nq,
-                Err(_) => break
-            }
+        while let Ok(nq) = q.tail() {
+            q = nq;
         }
     })
 }
@@ -42,6 +40,19 @@ fn bench_pf_queue_kill_batched<Queue: PfQueue<i32>>(b: &mut Bencher) {
     })
 }
 
+fn bench_pf_deque_init<Deque: PfDeque<i32>>(b: &mut Bencher) {
+    let mut d_full = Deque::new();
+    for i in 0..1000 {
+        d_full = d_full.snoc(i);
+    }
+    b.iter(|| {
+        let mut d = d_full.clone();
+        while let Ok(nd) = d.init() {
+            d = nd;
+        }
+    })
+}
+
 fn bench_pf_batched_queue_snoc(b: &mut Bencher) {
     bench_pf_queue_snoc::<PfBatchedQueue<i32>>(b);
 }
@@ -66,6 +77,58 @@ fn bench_pf_banker_queue_kill_batched(b: &mut Bencher) {
     bench_pf_queue_kill_batched::<PfBankerQueue<i32>>(b);
 }
 
+fn bench_pf_catenable_list_snoc(b: &mut Bencher) {
+    bench_pf_queue_snoc::<PfCatenableList<i32>>(b);
+}
+
+fn bench_pf_catenable_list_tail(b: &mut Bencher) {
+    bench_pf_queue_tail::<PfCatenableList<i32>>(b);
+}
+
+fn bench_pf_catenable_list_kill_batched(b: &mut Bencher) {
+    bench_pf_queue_kill_batched::<PfCatenableList<i32>>(b);
+}
+
+fn bench_pf_catenable_deque_snoc(b: &mut Bencher) {
+    bench_pf_queue_snoc::<PfCatenableDeque<i32>>(b);
+}
+
+fn bench_pf_catenable_deque_tail(b: &mut Bencher) {
+    bench_pf_queue_tail::<PfCatenableDeque<i32>>(b);
+}
+
+fn bench_pf_catenable_deque_kill_batched(b: &mut Bencher) {
+    bench_pf_queue_kill_batched::<PfCatenableDeque<i32>>(b);
+}
+
+fn bench_pf_catenable_deque_init(b: &mut Bencher) {
+    bench_pf_deque_init::<PfCatenableDeque<i32>>(b);
+}
+
+fn bench_pf_hood_melville_queue_snoc(b: &mut Bencher) {
+    bench_pf_queue_snoc::<PfHoodMelvilleQueue<i32>>(b);
+}
+
+fn bench_pf_hood_melville_queue_tail(b: &mut Bencher) {
+    bench_pf_queue_tail::<PfHoodMelvilleQueue<i32>>(b);
+}
+
+fn bench_pf_hood_melville_queue_kill_batched(b: &mut Bencher) {
+    bench_pf_queue_kill_batched::<PfHoodMelvilleQueue<i32>>(b);
+}
+
+fn bench_pf_physicist_queue_snoc(b: &mut Bencher) {
+    bench_pf_queue_snoc::<PfPhysicistQueue<i32>>(b);
+}
+
+fn bench_pf_physicist_queue_tail(b: &mut Bencher) {
+    bench_pf_queue_tail::<PfPhysicistQueue<i32>>(b);
+}
+
+fn bench_pf_physicist_queue_kill_batched(b: &mut Bencher) {
+    bench_pf_queue_kill_batched::<PfPhysicistQueue<i32>>(b);
+}
+
 fn bench_pf_real_time_queue_snoc(b: &mut Bencher) {
     bench_pf_queue_snoc::<PfRealTimeQueue<i32>>(b);
 }
@@ -78,15 +141,88 @@ fn bench_pf_real_time_queue_kill_batched(b: &mut Bencher) {
     bench_pf_queue_kill_batched::<PfRealTimeQueue<i32>>(b);
 }
 
+fn bench_pf_banker_deque_snoc(b: &mut Bencher) {
+    bench_pf_queue_snoc::<PfBankerDeque<i32>>(b);
+}
+
+fn bench_pf_banker_deque_tail(b: &mut Bencher) {
+    bench_pf_queue_tail::<PfBankerDeque<i32>>(b);
+}
+
+fn bench_pf_banker_deque_kill_batched(b: &mut Bencher) {
+    bench_pf_queue_kill_batched::<PfBankerDeque<i32>>(b);
+}
+
+fn bench_pf_banker_deque_init(b: &mut Bencher) {
+    bench_pf_deque_init::<PfBankerDeque<i32>>(b);
+}
+
+fn bench_pf_real_time_deque_snoc(b: &mut Bencher) {
+    bench_pf_queue_snoc::<PfRealTimeDeque<i32>>(b);
+}
+
+fn bench_pf_real_time_deque_tail(b: &mut Bencher) {
+    bench_pf_queue_tail::<PfRealTimeDeque<i32>>(b);
+}
+
+fn bench_pf_real_time_deque_kill_batched(b: &mut Bencher) {
+    bench_pf_queue_kill_batched::<PfRealTimeDeque<i32>>(b);
+}
+
+fn bench_pf_real_time_deque_init(b: &mut Bencher) {
+    bench_pf_deque_init::<PfRealTimeDeque<i32>>(b);
+}
+
+fn bench_pf_simple_catenable_deque_snoc(b: &mut Bencher) {
+    bench_pf_queue_snoc::<PfSimpleCatenableDeque<i32>>(b);
+}
+
+fn bench_pf_simple_catenable_deque_tail(b: &mut Bencher) {
+    bench_pf_queue_tail::<PfSimpleCatenableDeque<i32>>(b);
+}
+
+fn bench_pf_simple_catenable_deque_kill_batched(b: &mut Bencher) {
+    bench_pf_queue_kill_batched::<PfSimpleCatenableDeque<i32>>(b);
+}
+
+fn bench_pf_simple_catenable_deque_init(b: &mut Bencher) {
+    bench_pf_deque_init::<PfSimpleCatenableDeque<i32>>(b);
+}
+
 benchmark_group!(benches,
+                 bench_pf_banker_deque_init,
+                 bench_pf_banker_deque_kill_batched,
+                 bench_pf_banker_deque_snoc,
+                 bench_pf_banker_deque_tail,
                  bench_pf_banker_queue_kill_batched,
                  bench_pf_banker_queue_snoc,
                  bench_pf_banker_queue_tail,
                  bench_pf_batched_queue_kill_batched,
                  bench_pf_batched_queue_snoc,
                  bench_pf_batched_queue_tail,
+                 bench_pf_catenable_deque_init,
+                 bench_pf_catenable_deque_kill_batched,
+                 bench_pf_catenable_deque_snoc,
+                 bench_pf_catenable_deque_tail,
+                 bench_pf_catenable_list_kill_batched,
+                 bench_pf_catenable_list_snoc,
+                 bench_pf_catenable_list_tail,
+                 bench_pf_hood_melville_queue_kill_batched,
+                 bench_pf_hood_melville_queue_snoc,
+                 bench_pf_hood_melville_queue_tail,
+                 bench_pf_physicist_queue_kill_batched,
+                 bench_pf_physicist_queue_snoc,
+                 bench_pf_physicist_queue_tail,
+                 bench_pf_real_time_deque_init,
+                 bench_pf_real_time_deque_kill_batched,
+                 bench_pf_real_time_deque_snoc,
+                 bench_pf_real_time_deque_tail,
                  bench_pf_real_time_queue_kill_batched,
                  bench_pf_real_time_queue_snoc,
-                 bench_pf_real_time_queue_tail
+                 bench_pf_real_time_queue_tail,
+                 bench_pf_simple_catenable_deque_init,
+                 bench_pf_simple_catenable_deque_kill_batched,
+                 bench_pf_simple_catenable_deque_snoc,
+                 bench_pf_simple_catenable_deque_tail
 );
 benchmark_main!(benches);
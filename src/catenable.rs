@@ -0,0 +1,918 @@
+use std::fmt;
+use std::fmt::Debug;
+use std::fmt::Display;
+
+use lazy::Shareable;
+use lazy::Thunk;
+use queue::PfBankerDeque;
+use queue::PfBankerQueue;
+use queue::PfDeque;
+use queue::PfQueue;
+
+// 10.2.1 Catenable list
+#[derive(Clone, Debug)]
+struct CatSusp<'a, T: 'a + Clone + Display + Debug>(
+    Thunk<'a, PfCatenableList<'a, T>>);
+
+impl<'a, T: 'a + Clone + Display + Debug> Display for CatSusp<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        f.write_str("CatSusp")
+    }
+}
+
+#[derive(Clone, Debug)]
+struct CatNode<'a, T: 'a + Clone + Display + Debug> {
+    value: T,
+    queue: PfBankerQueue<'a, CatSusp<'a, T>>
+}
+
+#[derive(Clone, Debug)]
+pub struct PfCatenableList<'a, T: 'a + Clone + Display + Debug> {
+    node: Option<CatNode<'a, T>>
+}
+
+impl<'a, T: 'a + Clone + Display + Debug + Shareable> PfCatenableList<'a, T> {
+    fn singleton(v: T) -> Self {
+        PfCatenableList {
+            node: Some(CatNode { value: v, queue: PfBankerQueue::new() })
+        }
+    }
+
+    fn link(&self, s: Thunk<'a, Self>) -> Self {
+        match self.node {
+            Some(ref n) => PfCatenableList {
+                node: Some(CatNode {
+                    value: n.value.clone(),
+                    queue: n.queue.snoc(CatSusp(s))
+                })
+            },
+            None => unreachable!("link to empty catenable list")
+        }
+    }
+
+    fn link_all(q: &PfBankerQueue<'a, CatSusp<'a, T>>) -> Self {
+        let t = q.head_ref().unwrap().0.eval();
+        let qd = q.tail().unwrap();
+        if qd.is_empty() {
+            t
+        } else {
+            t.link(lazy!(Self::link_all(&qd)))
+        }
+    }
+
+    pub fn cons(&self, v: T) -> Self {
+        Self::singleton(v).append(self)
+    }
+
+    pub fn append(&self, l: &Self) -> Self {
+        if l.is_empty() {
+            self.clone()
+        } else if self.is_empty() {
+            l.clone()
+        } else {
+            let l = l.clone();
+            self.link(lazy!(l))
+        }
+    }
+}
+
+impl<'a, T: 'a + Clone + Display + Debug + Shareable> PfQueue<T>
+    for PfCatenableList<'a, T> {
+    fn new() -> Self {
+        PfCatenableList { node: None }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.node.is_none()
+    }
+
+    fn snoc(&self, v: T) -> Self {
+        self.append(&Self::singleton(v))
+    }
+
+    fn head_ref(&self) -> Result<&T, &str> {
+        match self.node {
+            Some(ref n) => Ok(&n.value),
+            None => Err("head for empty catenable list")
+        }
+    }
+
+    fn tail(&self) -> Result<Self, &str> {
+        match self.node {
+            Some(ref n) => {
+                if n.queue.is_empty() {
+                    Ok(Self::new())
+                } else {
+                    Ok(Self::link_all(&n.queue))
+                }
+            }
+            None => Err("tail for empty catenable list")
+        }
+    }
+}
+
+// 11.1 Simple catenable deque
+//
+// The middle of a deep node holds deques of elements rather than
+// elements. Both levels share one element type so that the recursion
+// stays at a single type.
+#[derive(Clone, Debug)]
+enum SimpleElem<'a, T: 'a + Clone + Display + Debug> {
+    Item(T),
+    Deque(PfBankerDeque<'a, SimpleElem<'a, T>>)
+}
+
+impl<'a, T: 'a + Clone + Display + Debug> Display for SimpleElem<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            SimpleElem::Item(ref v) => Display::fmt(v, f),
+            SimpleElem::Deque(_) => f.write_str("Deque")
+        }
+    }
+}
+
+impl<'a, T: 'a + Clone + Display + Debug> SimpleElem<'a, T> {
+    fn item(&self) -> &T {
+        match *self {
+            SimpleElem::Item(ref v) => v,
+            SimpleElem::Deque(_) => unreachable!("deque at top level")
+        }
+    }
+
+    fn deque(&self) -> &SimpleDeque<'a, T> {
+        match *self {
+            SimpleElem::Deque(ref d) => d,
+            SimpleElem::Item(_) => unreachable!("item in middle level")
+        }
+    }
+}
+
+type SimpleDeque<'a, T> = PfBankerDeque<'a, SimpleElem<'a, T>>;
+
+#[derive(Clone, Debug)]
+enum SimpleCat<'a, T: 'a + Clone + Display + Debug> {
+    Shallow(SimpleDeque<'a, T>),
+    Deep(SimpleDeque<'a, T>, Thunk<'a, SimpleCat<'a, T>>,
+         SimpleDeque<'a, T>)
+}
+
+fn too_small<T: Clone + Display + Debug, D: PfDeque<T>>(d: &D) -> bool {
+    match d.tail() {
+        Ok(t) => t.is_empty(),
+        Err(_) => true
+    }
+}
+
+impl<'a, T: 'a + Clone + Display + Debug + Shareable> SimpleCat<'a, T> {
+    fn empty() -> Self {
+        SimpleCat::Shallow(PfBankerDeque::new())
+    }
+
+    fn is_empty(&self) -> bool {
+        match *self {
+            SimpleCat::Shallow(ref d) => d.is_empty(),
+            SimpleCat::Deep(..) => false
+        }
+    }
+
+    fn dappend_l(d1: &SimpleDeque<'a, T>, d2: &SimpleDeque<'a, T>)
+                 -> SimpleDeque<'a, T> {
+        match d1.head() {
+            Ok(x) => d2.cons(x),
+            Err(_) => d2.clone()
+        }
+    }
+
+    fn dappend_r(d1: &SimpleDeque<'a, T>, d2: &SimpleDeque<'a, T>)
+                 -> SimpleDeque<'a, T> {
+        match d2.head() {
+            Ok(x) => d1.snoc(x),
+            Err(_) => d1.clone()
+        }
+    }
+
+    fn cons(&self, x: SimpleElem<'a, T>) -> Self {
+        match *self {
+            SimpleCat::Shallow(ref d) => SimpleCat::Shallow(d.cons(x)),
+            SimpleCat::Deep(ref f, ref m, ref r) =>
+                SimpleCat::Deep(f.cons(x), m.clone(), r.clone())
+        }
+    }
+
+    fn snoc(&self, x: SimpleElem<'a, T>) -> Self {
+        match *self {
+            SimpleCat::Shallow(ref d) => SimpleCat::Shallow(d.snoc(x)),
+            SimpleCat::Deep(ref f, ref m, ref r) =>
+                SimpleCat::Deep(f.clone(), m.clone(), r.snoc(x))
+        }
+    }
+
+    fn head(&self) -> Option<&SimpleElem<'a, T>> {
+        match *self {
+            SimpleCat::Shallow(ref d) => d.head_ref().ok(),
+            SimpleCat::Deep(ref f, _, _) => f.head_ref().ok()
+        }
+    }
+
+    fn last(&self) -> Option<&SimpleElem<'a, T>> {
+        match *self {
+            SimpleCat::Shallow(ref d) => d.last_ref().ok(),
+            SimpleCat::Deep(_, _, ref r) => r.last_ref().ok()
+        }
+    }
+
+    fn tail(&self) -> Option<Self> {
+        match *self {
+            SimpleCat::Shallow(ref d) => d.tail().ok().map(SimpleCat::Shallow),
+            SimpleCat::Deep(ref f, ref m, ref r) => {
+                let fd = f.tail().unwrap();
+                if !too_small(&fd) {
+                    return Some(SimpleCat::Deep(fd, m.clone(), r.clone()));
+                }
+                let mv = m.eval();
+                match mv.head() {
+                    Some(d) => {
+                        let fd = Self::dappend_l(&fd, d.deque());
+                        Some(SimpleCat::Deep(fd, lazy!(mv.tail().unwrap()),
+                                             r.clone()))
+                    }
+                    None => Some(SimpleCat::Shallow(Self::dappend_l(&fd, r)))
+                }
+            }
+        }
+    }
+
+    fn init(&self) -> Option<Self> {
+        match *self {
+            SimpleCat::Shallow(ref d) => d.init().ok().map(SimpleCat::Shallow),
+            SimpleCat::Deep(ref f, ref m, ref r) => {
+                let rd = r.init().unwrap();
+                if !too_small(&rd) {
+                    return Some(SimpleCat::Deep(f.clone(), m.clone(), rd));
+                }
+                let mv = m.eval();
+                match mv.last() {
+                    Some(d) => {
+                        let rd = Self::dappend_r(d.deque(), &rd);
+                        Some(SimpleCat::Deep(f.clone(),
+                                             lazy!(mv.init().unwrap()), rd))
+                    }
+                    None => Some(SimpleCat::Shallow(Self::dappend_r(f, &rd)))
+                }
+            }
+        }
+    }
+
+    fn append(&self, other: &Self) -> Self {
+        use self::SimpleCat::*;
+        match (self, other) {
+            (Shallow(d1), Shallow(d2)) => {
+                if too_small(d1) {
+                    Shallow(Self::dappend_l(d1, d2))
+                } else if too_small(d2) {
+                    Shallow(Self::dappend_r(d1, d2))
+                } else {
+                    Deep(d1.clone(), lazy!(Self::empty()), d2.clone())
+                }
+            }
+            (Shallow(d), Deep(f, m, r)) => {
+                if too_small(d) {
+                    Deep(Self::dappend_l(d, f), m.clone(), r.clone())
+                } else {
+                    let (f, m) = (f.clone(), m.clone());
+                    Deep(d.clone(),
+                         lazy!(m.force().cons(SimpleElem::Deque(f))),
+                         r.clone())
+                }
+            }
+            (Deep(f, m, r), Shallow(d)) => {
+                if too_small(d) {
+                    Deep(f.clone(), m.clone(), Self::dappend_r(r, d))
+                } else {
+                    let (m, r) = (m.clone(), r.clone());
+                    Deep(f.clone(),
+                         lazy!(m.force().snoc(SimpleElem::Deque(r))),
+                         d.clone())
+                }
+            }
+            (Deep(f1, m1, r1), Deep(f2, m2, r2)) => {
+                let (m1, r1) = (m1.clone(), r1.clone());
+                let (f2, m2) = (f2.clone(), m2.clone());
+                Deep(f1.clone(),
+                     lazy!(m1.force().snoc(SimpleElem::Deque(r1))
+                           .append(&m2.force()
+                                   .cons(SimpleElem::Deque(f2)))),
+                     r2.clone())
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PfSimpleCatenableDeque<'a, T: 'a + Clone + Display + Debug> {
+    cat: SimpleCat<'a, T>
+}
+
+impl<'a, T: 'a + Clone + Display + Debug + Shareable>
+    PfSimpleCatenableDeque<'a, T> {
+    pub fn append(&self, d: &Self) -> Self {
+        PfSimpleCatenableDeque { cat: self.cat.append(&d.cat) }
+    }
+}
+
+impl<'a, T: 'a + Clone + Display + Debug + Shareable> PfQueue<T>
+    for PfSimpleCatenableDeque<'a, T> {
+    fn new() -> Self {
+        PfSimpleCatenableDeque { cat: SimpleCat::empty() }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.cat.is_empty()
+    }
+
+    fn snoc(&self, v: T) -> Self {
+        PfSimpleCatenableDeque { cat: self.cat.snoc(SimpleElem::Item(v)) }
+    }
+
+    fn head_ref(&self) -> Result<&T, &str> {
+        match self.cat.head() {
+            Some(x) => Ok(x.item()),
+            None => Err("head for empty simple catenable deque")
+        }
+    }
+
+    fn tail(&self) -> Result<Self, &str> {
+        match self.cat.tail() {
+            Some(cat) => Ok(PfSimpleCatenableDeque { cat }),
+            None => Err("tail for empty simple catenable deque")
+        }
+    }
+}
+
+impl<'a, T: 'a + Clone + Display + Debug + Shareable> PfDeque<T>
+    for PfSimpleCatenableDeque<'a, T> {
+    fn cons(&self, v: T) -> Self {
+        PfSimpleCatenableDeque { cat: self.cat.cons(SimpleElem::Item(v)) }
+    }
+
+    fn last_ref(&self) -> Result<&T, &str> {
+        match self.cat.last() {
+            Some(x) => Ok(x.item()),
+            None => Err("last for empty simple catenable deque")
+        }
+    }
+
+    fn init(&self) -> Result<Self, &str> {
+        match self.cat.init() {
+            Some(cat) => Ok(PfSimpleCatenableDeque { cat }),
+            None => Err("init for empty simple catenable deque")
+        }
+    }
+}
+
+// 11.2 Catenable deque
+//
+// As with the simple version, the elements of the middle levels are
+// folded into the element type so that the recursion stays uniform.
+#[derive(Clone, Debug)]
+enum CmpdElem<'a, T: 'a + Clone + Display + Debug> {
+    Item(T),
+    Simple(CmpdDeque<'a, T>),
+    Cmpd(CmpdDeque<'a, T>, Thunk<'a, Cat<'a, T>>, CmpdDeque<'a, T>)
+}
+
+impl<'a, T: 'a + Clone + Display + Debug> Display for CmpdElem<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            CmpdElem::Item(ref v) => Display::fmt(v, f),
+            CmpdElem::Simple(_) => f.write_str("Simple"),
+            CmpdElem::Cmpd(..) => f.write_str("Cmpd")
+        }
+    }
+}
+
+impl<'a, T: 'a + Clone + Display + Debug> CmpdElem<'a, T> {
+    fn item(&self) -> &T {
+        match *self {
+            CmpdElem::Item(ref v) => v,
+            _ => unreachable!("compound element at top level")
+        }
+    }
+}
+
+type CmpdDeque<'a, T> = PfBankerDeque<'a, CmpdElem<'a, T>>;
+
+#[derive(Clone, Debug)]
+enum Cat<'a, T: 'a + Clone + Display + Debug> {
+    Shallow(CmpdDeque<'a, T>),
+    Deep {
+        f: CmpdDeque<'a, T>,
+        a: Thunk<'a, Cat<'a, T>>,
+        m: CmpdDeque<'a, T>,
+        b: Thunk<'a, Cat<'a, T>>,
+        r: CmpdDeque<'a, T>,
+    }
+}
+
+fn shorter_than<T: Clone + Display + Debug, D: PfDeque<T>>(d: &D, n: usize)
+                                                          -> bool {
+    let mut d = d.clone();
+    for _ in 0..n {
+        match d.tail() {
+            Ok(t) => d = t,
+            Err(_) => return true
+        }
+    }
+    false
+}
+
+impl<'a, T: 'a + Clone + Display + Debug + Shareable> Cat<'a, T> {
+    fn empty() -> Self {
+        Cat::Shallow(PfBankerDeque::new())
+    }
+
+    fn is_empty(&self) -> bool {
+        match *self {
+            Cat::Shallow(ref d) => d.is_empty(),
+            Cat::Deep { .. } => false
+        }
+    }
+
+    fn share(f: &CmpdDeque<'a, T>, r: &CmpdDeque<'a, T>)
+             -> (CmpdDeque<'a, T>, CmpdDeque<'a, T>, CmpdDeque<'a, T>) {
+        let m = PfBankerDeque::new()
+            .cons(r.head().unwrap())
+            .cons(f.last().unwrap());
+        (f.init().unwrap(), m, r.tail().unwrap())
+    }
+
+    fn dappend_l(d1: &CmpdDeque<'a, T>, d2: &CmpdDeque<'a, T>)
+                 -> CmpdDeque<'a, T> {
+        let mut d1 = d1.clone();
+        let mut d2 = d2.clone();
+        while let Ok(x) = d1.last() {
+            d2 = d2.cons(x);
+            d1 = d1.init().unwrap();
+        }
+        d2
+    }
+
+    fn dappend_r(d1: &CmpdDeque<'a, T>, d2: &CmpdDeque<'a, T>)
+                 -> CmpdDeque<'a, T> {
+        let mut d1 = d1.clone();
+        let mut d2 = d2.clone();
+        while let Ok(x) = d2.head() {
+            d1 = d1.snoc(x);
+            d2 = d2.tail().unwrap();
+        }
+        d1
+    }
+
+    fn cons(&self, x: CmpdElem<'a, T>) -> Self {
+        match *self {
+            Cat::Shallow(ref d) => Cat::Shallow(d.cons(x)),
+            Cat::Deep { ref f, ref a, ref m, ref b, ref r } => Cat::Deep {
+                f: f.cons(x),
+                a: a.clone(),
+                m: m.clone(),
+                b: b.clone(),
+                r: r.clone()
+            }
+        }
+    }
+
+    fn snoc(&self, x: CmpdElem<'a, T>) -> Self {
+        match *self {
+            Cat::Shallow(ref d) => Cat::Shallow(d.snoc(x)),
+            Cat::Deep { ref f, ref a, ref m, ref b, ref r } => Cat::Deep {
+                f: f.clone(),
+                a: a.clone(),
+                m: m.clone(),
+                b: b.clone(),
+                r: r.snoc(x)
+            }
+        }
+    }
+
+    fn head(&self) -> Option<&CmpdElem<'a, T>> {
+        match *self {
+            Cat::Shallow(ref d) => d.head_ref().ok(),
+            Cat::Deep { ref f, .. } => f.head_ref().ok()
+        }
+    }
+
+    fn last(&self) -> Option<&CmpdElem<'a, T>> {
+        match *self {
+            Cat::Shallow(ref d) => d.last_ref().ok(),
+            Cat::Deep { ref r, .. } => r.last_ref().ok()
+        }
+    }
+
+    fn replace_head(&self, x: CmpdElem<'a, T>) -> Self {
+        match *self {
+            Cat::Shallow(ref d) => Cat::Shallow(d.tail().unwrap().cons(x)),
+            Cat::Deep { ref f, ref a, ref m, ref b, ref r } => Cat::Deep {
+                f: f.tail().unwrap().cons(x),
+                a: a.clone(),
+                m: m.clone(),
+                b: b.clone(),
+                r: r.clone()
+            }
+        }
+    }
+
+    fn replace_last(&self, x: CmpdElem<'a, T>) -> Self {
+        match *self {
+            Cat::Shallow(ref d) => Cat::Shallow(d.init().unwrap().snoc(x)),
+            Cat::Deep { ref f, ref a, ref m, ref b, ref r } => Cat::Deep {
+                f: f.clone(),
+                a: a.clone(),
+                m: m.clone(),
+                b: b.clone(),
+                r: r.init().unwrap().snoc(x)
+            }
+        }
+    }
+
+    fn tail(&self) -> Option<Self> {
+        let (f, a, m, b, r) = match *self {
+            Cat::Shallow(ref d) => return d.tail().ok().map(Cat::Shallow),
+            Cat::Deep { ref f, ref a, ref m, ref b, ref r } => (f, a, m, b, r)
+        };
+        let fd = f.tail().unwrap();
+        if !shorter_than(f, 4) {
+            return Some(Cat::Deep {
+                f: fd,
+                a: a.clone(),
+                m: m.clone(),
+                b: b.clone(),
+                r: r.clone()
+            });
+        }
+        let av = a.eval();
+        if let Some(x) = av.head().cloned() {
+            return Some(match x {
+                CmpdElem::Simple(d) => Cat::Deep {
+                    f: Self::dappend_l(&fd, &d),
+                    a: lazy!(av.tail().unwrap()),
+                    m: m.clone(),
+                    b: b.clone(),
+                    r: r.clone()
+                },
+                CmpdElem::Cmpd(fx, cx, rx) => Cat::Deep {
+                    f: Self::dappend_l(&fd, &fx),
+                    a: lazy!(cx.force().append(
+                        &av.replace_head(CmpdElem::Simple(rx)))),
+                    m: m.clone(),
+                    b: b.clone(),
+                    r: r.clone()
+                },
+                CmpdElem::Item(_) => unreachable!("item in middle level")
+            });
+        }
+        let bv = b.eval();
+        if let Some(x) = bv.head().cloned() {
+            return Some(match x {
+                CmpdElem::Simple(d) => Cat::Deep {
+                    f: Self::dappend_l(&fd, m),
+                    a: lazy!(Self::empty()),
+                    m: d,
+                    b: lazy!(bv.tail().unwrap()),
+                    r: r.clone()
+                },
+                CmpdElem::Cmpd(fx, cx, rx) => Cat::Deep {
+                    f: Self::dappend_l(&fd, m),
+                    a: lazy!(cx.force().cons(CmpdElem::Simple(fx))),
+                    m: rx,
+                    b: lazy!(bv.tail().unwrap()),
+                    r: r.clone()
+                },
+                CmpdElem::Item(_) => unreachable!("item in middle level")
+            });
+        }
+        Some(Cat::Shallow(Self::dappend_l(&fd, m))
+             .append(&Cat::Shallow(r.clone())))
+    }
+
+    fn init(&self) -> Option<Self> {
+        let (f, a, m, b, r) = match *self {
+            Cat::Shallow(ref d) => return d.init().ok().map(Cat::Shallow),
+            Cat::Deep { ref f, ref a, ref m, ref b, ref r } => (f, a, m, b, r)
+        };
+        let rd = r.init().unwrap();
+        if !shorter_than(r, 4) {
+            return Some(Cat::Deep {
+                f: f.clone(),
+                a: a.clone(),
+                m: m.clone(),
+                b: b.clone(),
+                r: rd
+            });
+        }
+        let bv = b.eval();
+        if let Some(x) = bv.last().cloned() {
+            return Some(match x {
+                CmpdElem::Simple(d) => Cat::Deep {
+                    f: f.clone(),
+                    a: a.clone(),
+                    m: m.clone(),
+                    b: lazy!(bv.init().unwrap()),
+                    r: Self::dappend_r(&d, &rd)
+                },
+                CmpdElem::Cmpd(fx, cx, rx) => Cat::Deep {
+                    f: f.clone(),
+                    a: a.clone(),
+                    m: m.clone(),
+                    b: lazy!(bv.replace_last(CmpdElem::Simple(fx))
+                             .append(cx.force())),
+                    r: Self::dappend_r(&rx, &rd)
+                },
+                CmpdElem::Item(_) => unreachable!("item in middle level")
+            });
+        }
+        let av = a.eval();
+        if let Some(x) = av.last().cloned() {
+            return Some(match x {
+                CmpdElem::Simple(d) => Cat::Deep {
+                    f: f.clone(),
+                    a: lazy!(av.init().unwrap()),
+                    m: d,
+                    b: lazy!(Self::empty()),
+                    r: Self::dappend_r(m, &rd)
+                },
+                CmpdElem::Cmpd(fx, cx, rx) => Cat::Deep {
+                    f: f.clone(),
+                    a: lazy!(av.init().unwrap()),
+                    m: fx,
+                    b: lazy!(cx.force().snoc(CmpdElem::Simple(rx))),
+                    r: Self::dappend_r(m, &rd)
+                },
+                CmpdElem::Item(_) => unreachable!("item in middle level")
+            });
+        }
+        Some(Cat::Shallow(f.clone())
+             .append(&Cat::Shallow(Self::dappend_r(m, &rd))))
+    }
+
+    fn append(&self, other: &Self) -> Self {
+        match (self, other) {
+            (Cat::Shallow(d1), Cat::Shallow(d2)) => {
+                if shorter_than(d1, 4) {
+                    Cat::Shallow(Self::dappend_l(d1, d2))
+                } else if shorter_than(d2, 4) {
+                    Cat::Shallow(Self::dappend_r(d1, d2))
+                } else {
+                    let (f, m, r) = Self::share(d1, d2);
+                    Cat::Deep {
+                        f,
+                        a: lazy!(Self::empty()),
+                        m,
+                        b: lazy!(Self::empty()),
+                        r
+                    }
+                }
+            }
+            (Cat::Shallow(d), Cat::Deep { f, a, m, b, r }) => {
+                if shorter_than(d, 4) {
+                    Cat::Deep {
+                        f: Self::dappend_l(d, f),
+                        a: a.clone(),
+                        m: m.clone(),
+                        b: b.clone(),
+                        r: r.clone()
+                    }
+                } else {
+                    let (f, a) = (f.clone(), a.clone());
+                    Cat::Deep {
+                        f: d.clone(),
+                        a: lazy!(a.force().cons(CmpdElem::Simple(f))),
+                        m: m.clone(),
+                        b: b.clone(),
+                        r: r.clone()
+                    }
+                }
+            }
+            (Cat::Deep { f, a, m, b, r }, Cat::Shallow(d)) => {
+                if shorter_than(d, 4) {
+                    Cat::Deep {
+                        f: f.clone(),
+                        a: a.clone(),
+                        m: m.clone(),
+                        b: b.clone(),
+                        r: Self::dappend_r(r, d)
+                    }
+                } else {
+                    let (b, r) = (b.clone(), r.clone());
+                    Cat::Deep {
+                        f: f.clone(),
+                        a: a.clone(),
+                        m: m.clone(),
+                        b: lazy!(b.force().snoc(CmpdElem::Simple(r))),
+                        r: d.clone()
+                    }
+                }
+            }
+            (Cat::Deep { f: f1, a: a1, m: m1, b: b1, r: r1 },
+             Cat::Deep { f: f2, a: a2, m: m2, b: b2, r: r2 }) => {
+                let (r1, m, f2) = Self::share(r1, f2);
+                let (a1, m1, b1) = (a1.clone(), m1.clone(), b1.clone());
+                let (a2, m2, b2) = (a2.clone(), m2.clone(), b2.clone());
+                Cat::Deep {
+                    f: f1.clone(),
+                    a: lazy!(a1.force().snoc(
+                        CmpdElem::Cmpd(m1, b1, r1))),
+                    m,
+                    b: lazy!(b2.force().cons(
+                        CmpdElem::Cmpd(f2, a2, m2))),
+                    r: r2.clone()
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PfCatenableDeque<'a, T: 'a + Clone + Display + Debug> {
+    cat: Cat<'a, T>
+}
+
+impl<'a, T: 'a + Clone + Display + Debug + Shareable> PfCatenableDeque<'a, T> {
+    pub fn append(&self, d: &Self) -> Self {
+        PfCatenableDeque { cat: self.cat.append(&d.cat) }
+    }
+}
+
+impl<'a, T: 'a + Clone + Display + Debug + Shareable> PfQueue<T>
+    for PfCatenableDeque<'a, T> {
+    fn new() -> Self {
+        PfCatenableDeque { cat: Cat::empty() }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.cat.is_empty()
+    }
+
+    fn snoc(&self, v: T) -> Self {
+        PfCatenableDeque { cat: self.cat.snoc(CmpdElem::Item(v)) }
+    }
+
+    fn head_ref(&self) -> Result<&T, &str> {
+        match self.cat.head() {
+            Some(x) => Ok(x.item()),
+            None => Err("head for empty catenable deque")
+        }
+    }
+
+    fn tail(&self) -> Result<Self, &str> {
+        match self.cat.tail() {
+            Some(cat) => Ok(PfCatenableDeque { cat }),
+            None => Err("tail for empty catenable deque")
+        }
+    }
+}
+
+impl<'a, T: 'a + Clone + Display + Debug + Shareable> PfDeque<T>
+    for PfCatenableDeque<'a, T> {
+    fn cons(&self, v: T) -> Self {
+        PfCatenableDeque { cat: self.cat.cons(CmpdElem::Item(v)) }
+    }
+
+    fn last_ref(&self) -> Result<&T, &str> {
+        match self.cat.last() {
+            Some(x) => Ok(x.item()),
+            None => Err("last for empty catenable deque")
+        }
+    }
+
+    fn init(&self) -> Result<Self, &str> {
+        match self.cat.init() {
+            Some(cat) => Ok(PfCatenableDeque { cat }),
+            None => Err("init for empty catenable deque")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+
+    fn to_vec<'a>(l: &PfCatenableList<'a, i32>) -> Vec<i32> {
+        let mut r = Vec::new();
+        let mut l = l.clone();
+        while let Ok(v) = l.head() {
+            r.push(v);
+            l = l.tail().unwrap();
+        }
+        r
+    }
+
+    #[test]
+    fn test_pf_catenable_list() {
+        let l1 = PfCatenableList::new();
+        assert!(l1.is_empty());
+        assert!(l1.head().is_err());
+        assert!(l1.tail().is_err());
+        let l2 = l1.snoc(2).snoc(3).cons(1);
+        assert_eq!(vec![1, 2, 3], to_vec(&l2));
+        let l3 = l2.append(&l2.snoc(4)).append(&l1);
+        assert_eq!(vec![1, 2, 3, 1, 2, 3, 4], to_vec(&l3));
+        assert_eq!(vec![1, 2, 3], to_vec(&l2));
+
+        let mut l = PfCatenableList::new();
+        let mut expected = Vec::new();
+        for i in 0..100 {
+            let chunk = PfCatenableList::new().snoc(i).snoc(i + 1000);
+            if i % 2 == 0 {
+                l = l.append(&chunk);
+                expected.push(i);
+                expected.push(i + 1000);
+            } else {
+                l = chunk.append(&l);
+                expected.insert(0, i + 1000);
+                expected.insert(0, i);
+            }
+        }
+        assert_eq!(expected, to_vec(&l));
+    }
+
+    fn test_catenable_deque<D, F>(append: F)
+        where D: PfDeque<i32>, F: Fn(&D, &D) -> D {
+        let mut ds: Vec<(D, VecDeque<i32>)> = vec![(D::new(), VecDeque::new())];
+        let mut x: i32 = 1;
+        for i in 0..3000 {
+            x = (x * 1103 + 12345) % 10007;
+            let k = (x as usize / 7) % ds.len();
+            let (d, mut model) = ds[k].clone();
+            let d = match x % 7 {
+                0 => {
+                    model.push_front(i);
+                    d.cons(i)
+                }
+                1 => {
+                    model.push_back(i);
+                    d.snoc(i)
+                }
+                2 => {
+                    assert_eq!(model.pop_front(), d.head().ok());
+                    d.tail().unwrap_or_else(|_| d.clone())
+                }
+                3 => {
+                    assert_eq!(model.pop_back(), d.last().ok());
+                    d.init().unwrap_or_else(|_| d.clone())
+                }
+                _ => {
+                    let j = (x as usize / 3) % ds.len();
+                    let (d2, model2) = ds[j].clone();
+                    model.extend(model2);
+                    append(&d, &d2)
+                }
+            };
+            assert_eq!(model.is_empty(), d.is_empty());
+            assert_eq!(model.front(), d.head().ok().as_ref());
+            assert_eq!(model.back(), d.last().ok().as_ref());
+            ds.push((d, model));
+        }
+        for (d, model) in ds.into_iter().rev().take(100) {
+            let mut d = d;
+            for v in model {
+                assert_eq!(v, d.head().unwrap());
+                d = d.tail().unwrap();
+            }
+            assert!(d.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_pf_simple_catenable_deque() {
+        test_catenable_deque::<PfSimpleCatenableDeque<i32>, _>(
+            |d1, d2| d1.append(d2));
+    }
+
+    #[test]
+    fn test_pf_catenable_deque() {
+        test_catenable_deque::<PfCatenableDeque<i32>, _>(
+            |d1, d2| d1.append(d2));
+    }
+
+    #[test]
+    fn test_head_ref() {
+        let a = || "a".to_string();
+        let b = || "b".to_string();
+        let l = PfCatenableList::new().snoc(a()).snoc(b());
+        assert_eq!(Ok(&a()), l.head_ref());
+        assert!(PfCatenableList::<String>::new().head_ref().is_err());
+
+        let d = PfSimpleCatenableDeque::new().snoc(a()).snoc(b());
+        assert_eq!(Ok(&a()), d.head_ref());
+        assert_eq!(Ok(&b()), d.last_ref());
+        let d = d.append(&d);
+        assert_eq!(Ok(&b()), d.init().unwrap().init().unwrap().last_ref());
+
+        let d = PfCatenableDeque::new().snoc(a()).snoc(b());
+        assert_eq!(Ok(&a()), d.head_ref());
+        assert_eq!(Ok(&b()), d.last_ref());
+        let d = d.append(&d);
+        assert_eq!(Ok(&b()), d.init().unwrap().init().unwrap().last_ref());
+    }
+}
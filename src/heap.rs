@@ -1,16 +1,49 @@
+use std::cmp::Ordering;
+use std::fmt;
 use std::fmt::Debug;
 use std::fmt::Display;
-use std::rc::Rc;
 
+use lazy::Shareable;
+use lazy::Thunk;
 use list::PfList;
+use Ptr;
+use stream::Stream;
 
-trait PfHeap<T: Ord + Clone + Display + Debug>: Sized {
+pub trait PfHeap<T: Ord + Clone + Display + Debug>: Clone + Sized {
     fn new() -> Self;
     fn is_empty(&self) -> bool;
     fn insert(&self, v: T) -> Self;
     fn merge(&self, h: &Self) -> Self;
     fn find_min(&self) -> Result<T, &str>;
     fn delete_min(&self) -> Result<Self, &str>;
+
+    fn from_iter<I>(it: I) -> Self
+        where I: IntoIterator<Item=T> {
+        let mut h = Self::new();
+        for v in it {
+            h = h.insert(v);
+        }
+        h
+    }
+
+    fn into_sorted_vec(self) -> Vec<T> {
+        let mut r = Vec::new();
+        let mut h = self;
+        loop {
+            let v = match h.find_min() {
+                Ok(v) => v,
+                Err(_) => break
+            };
+            r.push(v);
+            h = h.delete_min().unwrap();
+        }
+        r
+    }
+}
+
+pub fn heap_sort<Heap, T>(v: Vec<T>) -> Vec<T>
+    where Heap: PfHeap<T>, T: Ord + Clone + Display + Debug {
+    Heap::from_iter(v).into_sorted_vec()
 }
 
 // 3.1 Leftish heap
@@ -20,20 +53,20 @@ pub enum PfLeftistHeap<T: Ord + Clone + Display + Debug> {
     Node {
         rank: i32,
         value: T,
-        left: Rc<PfLeftistHeap<T>>,
-        right: Rc<PfLeftistHeap<T>>,
+        left: Ptr<PfLeftistHeap<T>>,
+        right: Ptr<PfLeftistHeap<T>>,
     }
 }
 
 impl<T: Ord + Clone + Display + Debug> PfLeftistHeap<T> {
     fn rank(&self) -> i32 {
-        match self {
-            &PfLeftistHeap::Empty => 0,
-            &PfLeftistHeap::Node { rank, .. } => rank
+        match *self {
+            PfLeftistHeap::Empty => 0,
+            PfLeftistHeap::Node { rank, .. } => rank
         }
     }
 
-    fn make_heap(x: T, a: Rc<Self>, b: Rc<Self>) -> Self {
+    fn make_heap(x: T, a: Ptr<Self>, b: Ptr<Self>) -> Self {
         if a.rank() >= b.rank() {
             PfLeftistHeap::Node {
                 rank: b.rank() + 1,
@@ -54,12 +87,12 @@ impl<T: Ord + Clone + Display + Debug> PfLeftistHeap<T> {
 
 impl<T: Ord + Clone + Display + Debug> Clone for PfLeftistHeap<T> {
     fn clone(&self) -> Self {
-        match self {
-            &PfLeftistHeap::Empty => PfLeftistHeap::Empty,
-            &PfLeftistHeap::Node {
+        match *self {
+            PfLeftistHeap::Empty => PfLeftistHeap::Empty,
+            PfLeftistHeap::Node {
                 rank, ref value, ref left, ref right
             } => PfLeftistHeap::Node {
-                rank: rank,
+                rank,
                 value: value.clone(),
                 left: left.clone(),
                 right: right.clone()
@@ -74,19 +107,15 @@ impl<T: Ord + Clone + Display + Debug> PfHeap<T> for PfLeftistHeap<T> {
     }
 
     fn is_empty(&self) -> bool {
-        if let PfLeftistHeap::Empty = *self {
-            return true;
-        } else {
-            return false;
-        }
+        matches!(*self, PfLeftistHeap::Empty)
     }
 
     fn insert(&self, v: T) -> Self {
         self.merge(&PfLeftistHeap::Node {
             rank: 1,
             value: v,
-            left: Rc::new(PfLeftistHeap::Empty),
-            right: Rc::new(PfLeftistHeap::Empty)
+            left: Ptr::new(PfLeftistHeap::Empty),
+            right: Ptr::new(PfLeftistHeap::Empty)
         })
     }
 
@@ -95,36 +124,320 @@ impl<T: Ord + Clone + Display + Debug> PfHeap<T> for PfLeftistHeap<T> {
         match (self, h) {
             (&Empty, h) => h.clone(),
             (h, &Empty) => h.clone(),
-            (&Node { rank: _, value: ref v1, left: ref a1, right: ref b1 },
-             &Node { rank: _, value: ref v2, left: ref a2, right: ref b2 }
+            (Node { value: v1, left: a1, right: b1, .. },
+             Node { value: v2, left: a2, right: b2, .. }
             ) => {
                 if v1 < v2 {
                     PfLeftistHeap::make_heap(v1.clone(), a1.clone(),
-                                             Rc::new(b1.merge(h)))
+                                             Ptr::new(b1.merge(h)))
                 } else {
                     PfLeftistHeap::make_heap(v2.clone(), a2.clone(),
-                                             Rc::new(self.merge(b2)))
+                                             Ptr::new(self.merge(b2)))
                 }
             }
         }
     }
 
     fn find_min(&self) -> Result<T, &str> {
-        match self {
-            &PfLeftistHeap::Empty => Err("find_min for empty leftish heap"),
-            &PfLeftistHeap::Node { ref value, .. } => Ok(value.clone())
+        match *self {
+            PfLeftistHeap::Empty => Err("find_min for empty leftish heap"),
+            PfLeftistHeap::Node { ref value, .. } => Ok(value.clone())
         }
     }
 
     fn delete_min(&self) -> Result<Self, &str> {
-        match self {
-            &PfLeftistHeap::Empty => Err("delete_min for empty leftish heap"),
-            &PfLeftistHeap::Node { ref left, ref right, .. } =>
+        match *self {
+            PfLeftistHeap::Empty => Err("delete_min for empty leftish heap"),
+            PfLeftistHeap::Node { ref left, ref right, .. } =>
                 Ok(left.merge(right))
         }
     }
 }
 
+// 3.2 Binomial heap
+#[derive(Clone, Debug)]
+struct BinomialTree<T: Ord + Clone + Display + Debug> {
+    rank: i32,
+    value: T,
+    children: BinomialTrees<T>
+}
+
+type BinomialTrees<T> = PfList<Ptr<BinomialTree<T>>>;
+
+#[derive(Clone, Debug)]
+pub struct PfBinomialHeap<T: Ord + Clone + Display + Debug> {
+    trees: BinomialTrees<T>
+}
+
+impl<T: Ord + Clone + Display + Debug> PfBinomialHeap<T> {
+    fn link(t1: &Ptr<BinomialTree<T>>, t2: &Ptr<BinomialTree<T>>)
+            -> Ptr<BinomialTree<T>> {
+        if t1.value <= t2.value {
+            Ptr::new(BinomialTree {
+                rank: t1.rank + 1,
+                value: t1.value.clone(),
+                children: t1.children.push(t2.clone())
+            })
+        } else {
+            Ptr::new(BinomialTree {
+                rank: t2.rank + 1,
+                value: t2.value.clone(),
+                children: t2.children.push(t1.clone())
+            })
+        }
+    }
+
+    fn ins_tree(t: Ptr<BinomialTree<T>>, ts: &BinomialTrees<T>)
+                -> BinomialTrees<T> {
+        match ts.pop() {
+            Ok((t2, ts2)) => {
+                if t.rank < t2.rank {
+                    ts.push(t)
+                } else {
+                    Self::ins_tree(Self::link(&t, &t2), &ts2)
+                }
+            }
+            Err(_) => ts.push(t)
+        }
+    }
+
+    fn merge_trees(ts1: &BinomialTrees<T>, ts2: &BinomialTrees<T>)
+                   -> BinomialTrees<T> {
+        match (ts1.pop(), ts2.pop()) {
+            (Err(_), _) => ts2.clone(),
+            (_, Err(_)) => ts1.clone(),
+            (Ok((t1, ts1d)), Ok((t2, ts2d))) => {
+                if t1.rank < t2.rank {
+                    Self::merge_trees(&ts1d, ts2).push(t1)
+                } else if t2.rank < t1.rank {
+                    Self::merge_trees(ts1, &ts2d).push(t2)
+                } else {
+                    Self::ins_tree(Self::link(&t1, &t2),
+                                   &Self::merge_trees(&ts1d, &ts2d))
+                }
+            }
+        }
+    }
+
+    fn remove_min_tree(ts: &BinomialTrees<T>)
+                       -> Option<(Ptr<BinomialTree<T>>, BinomialTrees<T>)> {
+        let (t, tsd) = ts.pop().ok()?;
+        match Self::remove_min_tree(&tsd) {
+            Some((t2, ts2)) => {
+                if t.value <= t2.value {
+                    Some((t, tsd))
+                } else {
+                    Some((t2, ts2.push(t)))
+                }
+            }
+            None => Some((t, tsd))
+        }
+    }
+}
+
+impl<T: Ord + Clone + Display + Debug> PfHeap<T> for PfBinomialHeap<T> {
+    fn new() -> Self {
+        PfBinomialHeap { trees: PfList::new() }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.trees.is_empty()
+    }
+
+    fn insert(&self, v: T) -> Self {
+        let t = Ptr::new(BinomialTree {
+            rank: 0,
+            value: v,
+            children: PfList::new()
+        });
+        PfBinomialHeap { trees: Self::ins_tree(t, &self.trees) }
+    }
+
+    fn merge(&self, h: &Self) -> Self {
+        PfBinomialHeap { trees: Self::merge_trees(&self.trees, &h.trees) }
+    }
+
+    fn find_min(&self) -> Result<T, &str> {
+        match Self::remove_min_tree(&self.trees) {
+            Some((t, _)) => Ok(t.value.clone()),
+            None => Err("find_min for empty binomial heap")
+        }
+    }
+
+    fn delete_min(&self) -> Result<Self, &str> {
+        match Self::remove_min_tree(&self.trees) {
+            Some((t, ts)) => Ok(PfBinomialHeap {
+                trees: Self::merge_trees(&t.children.rev(), &ts)
+            }),
+            None => Err("delete_min for empty binomial heap")
+        }
+    }
+}
+
+// 5.4 Splay heap
+#[derive(Debug)]
+pub enum PfSplayHeap<T: Ord + Clone + Display + Debug> {
+    Empty,
+    Node {
+        left: Ptr<PfSplayHeap<T>>,
+        value: T,
+        right: Ptr<PfSplayHeap<T>>,
+    }
+}
+
+impl<T: Ord + Clone + Display + Debug> PfSplayHeap<T> {
+    fn node(left: Self, value: T, right: Self) -> Self {
+        PfSplayHeap::Node {
+            left: Ptr::new(left),
+            value,
+            right: Ptr::new(right)
+        }
+    }
+
+    fn partition(pivot: &T, t: &Self) -> (Self, Self) {
+        use self::PfSplayHeap::*;
+        match *t {
+            Empty => (Empty, Empty),
+            Node { ref left, ref value, ref right } => {
+                if value <= pivot {
+                    match **right {
+                        Empty => (t.clone(), Empty),
+                        Node { left: ref b1, value: ref y, right: ref b2 } => {
+                            if y <= pivot {
+                                let (small, big) = Self::partition(pivot, b2);
+                                let a = Node {
+                                    left: left.clone(),
+                                    value: value.clone(),
+                                    right: b1.clone()
+                                };
+                                (Self::node(a, y.clone(), small), big)
+                            } else {
+                                let (small, big) = Self::partition(pivot, b1);
+                                (Node {
+                                    left: left.clone(),
+                                    value: value.clone(),
+                                    right: Ptr::new(small)
+                                }, Node {
+                                    left: Ptr::new(big),
+                                    value: y.clone(),
+                                    right: b2.clone()
+                                })
+                            }
+                        }
+                    }
+                } else {
+                    match **left {
+                        Empty => (Empty, t.clone()),
+                        Node { left: ref a1, value: ref y, right: ref a2 } => {
+                            if y <= pivot {
+                                let (small, big) = Self::partition(pivot, a2);
+                                (Node {
+                                    left: a1.clone(),
+                                    value: y.clone(),
+                                    right: Ptr::new(small)
+                                }, Node {
+                                    left: Ptr::new(big),
+                                    value: value.clone(),
+                                    right: right.clone()
+                                })
+                            } else {
+                                let (small, big) = Self::partition(pivot, a1);
+                                let b = Node {
+                                    left: a2.clone(),
+                                    value: value.clone(),
+                                    right: right.clone()
+                                };
+                                (small, Self::node(big, y.clone(), b))
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<T: Ord + Clone + Display + Debug> Clone for PfSplayHeap<T> {
+    fn clone(&self) -> Self {
+        match *self {
+            PfSplayHeap::Empty => PfSplayHeap::Empty,
+            PfSplayHeap::Node {
+                ref left, ref value, ref right
+            } => PfSplayHeap::Node {
+                left: left.clone(),
+                value: value.clone(),
+                right: right.clone()
+            }
+        }
+    }
+}
+
+impl<T: Ord + Clone + Display + Debug> PfHeap<T> for PfSplayHeap<T> {
+    fn new() -> Self {
+        PfSplayHeap::Empty
+    }
+
+    fn is_empty(&self) -> bool {
+        matches!(*self, PfSplayHeap::Empty)
+    }
+
+    fn insert(&self, v: T) -> Self {
+        let (a, b) = Self::partition(&v, self);
+        Self::node(a, v, b)
+    }
+
+    fn merge(&self, h: &Self) -> Self {
+        match *self {
+            PfSplayHeap::Empty => h.clone(),
+            PfSplayHeap::Node { ref left, ref value, ref right } => {
+                let (ta, tb) = Self::partition(value, h);
+                Self::node(ta.merge(left), value.clone(), tb.merge(right))
+            }
+        }
+    }
+
+    fn find_min(&self) -> Result<T, &str> {
+        let mut t = self;
+        loop {
+            match *t {
+                PfSplayHeap::Empty =>
+                    return Err("find_min for empty splay heap"),
+                PfSplayHeap::Node { ref left, ref value, .. } => {
+                    if left.is_empty() {
+                        return Ok(value.clone());
+                    }
+                    t = left;
+                }
+            }
+        }
+    }
+
+    fn delete_min(&self) -> Result<Self, &str> {
+        use self::PfSplayHeap::*;
+        match *self {
+            Empty => Err("delete_min for empty splay heap"),
+            Node { left: ref a, value: ref y, right: ref c } => {
+                match **a {
+                    Empty => Ok((**c).clone()),
+                    Node { left: ref a, value: ref x, right: ref b } => {
+                        let c = Node {
+                            left: b.clone(),
+                            value: y.clone(),
+                            right: c.clone()
+                        };
+                        if a.is_empty() {
+                            Ok(c)
+                        } else {
+                            Ok(Self::node(a.delete_min().unwrap(),
+                                          x.clone(), c))
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
 // 5.5 Pairing heap
 #[derive(Debug)]
 pub enum PfPairingHeap<T: Ord + Clone + Display + Debug> {
@@ -153,9 +466,9 @@ impl<T: Ord + Clone + Display + Debug> PfPairingHeap<T> {
 
 impl<T: Ord + Clone + Display + Debug> Clone for PfPairingHeap<T> {
     fn clone(&self) -> Self {
-        match self {
-            &PfPairingHeap::Empty => PfPairingHeap::Empty,
-            &PfPairingHeap::Node {
+        match *self {
+            PfPairingHeap::Empty => PfPairingHeap::Empty,
+            PfPairingHeap::Node {
                 ref value, ref children
             } => PfPairingHeap::Node {
                 value: value.clone(),
@@ -171,11 +484,7 @@ impl<T: Ord + Clone + Display + Debug> PfHeap<T> for PfPairingHeap<T> {
     }
 
     fn is_empty(&self) -> bool {
-        if let PfPairingHeap::Empty = *self {
-            return true;
-        } else {
-            return false;
-        }
+        matches!(*self, PfPairingHeap::Empty)
     }
 
     fn insert(&self, v: T) -> Self {
@@ -190,8 +499,8 @@ impl<T: Ord + Clone + Display + Debug> PfHeap<T> for PfPairingHeap<T> {
         match (self, h) {
             (&Empty, h) => h.clone(),
             (h, &Empty) => h.clone(),
-            (&Node { value: ref v1, children: ref hs1 },
-             &Node { value: ref v2, children: ref hs2 }
+            (Node { value: v1, children: hs1 },
+             Node { value: v2, children: hs2 }
             ) => {
                 if v1 < v2 {
                     PfPairingHeap::Node {
@@ -215,21 +524,606 @@ impl<T: Ord + Clone + Display + Debug> PfHeap<T> for PfPairingHeap<T> {
     }
 
     fn find_min(&self) -> Result<T, &str> {
-        match self {
-            &PfPairingHeap::Empty => Err("find_min for empty pairing heap"),
-            &PfPairingHeap::Node { ref value, .. } => Ok(value.clone())
+        match *self {
+            PfPairingHeap::Empty => Err("find_min for empty pairing heap"),
+            PfPairingHeap::Node { ref value, .. } => Ok(value.clone())
         }
     }
 
     fn delete_min(&self) -> Result<Self, &str> {
-        match self {
-            &PfPairingHeap::Empty => Err("delete_min for empty pairing heap"),
-            &PfPairingHeap::Node { ref children, .. } =>
+        match *self {
+            PfPairingHeap::Empty => Err("delete_min for empty pairing heap"),
+            PfPairingHeap::Node { ref children, .. } =>
                 Ok(Self::merge_pairs(children.clone()))
         }
     }
 }
 
+// 6.5 Lazy pairing heap
+#[derive(Debug)]
+pub enum PfLazyPairingHeap<'a, T: 'a + Ord + Clone + Display + Debug> {
+    Empty,
+    Node {
+        value: T,
+        odd: Ptr<PfLazyPairingHeap<'a, T>>,
+        rest: Thunk<'a, PfLazyPairingHeap<'a, T>>
+    }
+}
+
+impl<'a, T: 'a + Ord + Clone + Display + Debug + Shareable>
+    PfLazyPairingHeap<'a, T> {
+    fn link(&self, a: &Self) -> Self {
+        use self::PfLazyPairingHeap::*;
+        match *self {
+            Empty => a.clone(),
+            Node { ref value, ref odd, ref rest } => {
+                if odd.is_empty() {
+                    Node {
+                        value: value.clone(),
+                        odd: Ptr::new(a.clone()),
+                        rest: rest.clone()
+                    }
+                } else {
+                    let a = a.clone();
+                    let b = odd.clone();
+                    let m = rest.clone();
+                    Node {
+                        value: value.clone(),
+                        odd: Ptr::new(Empty),
+                        rest: lazy!(a.merge(&b).merge(m.force()))
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<'a, T: 'a + Ord + Clone + Display + Debug> Clone
+    for PfLazyPairingHeap<'a, T> {
+    fn clone(&self) -> Self {
+        match *self {
+            PfLazyPairingHeap::Empty => PfLazyPairingHeap::Empty,
+            PfLazyPairingHeap::Node {
+                ref value, ref odd, ref rest
+            } => PfLazyPairingHeap::Node {
+                value: value.clone(),
+                odd: odd.clone(),
+                rest: rest.clone()
+            }
+        }
+    }
+}
+
+impl<'a, T: 'a + Ord + Clone + Display + Debug + Shareable> PfHeap<T>
+    for PfLazyPairingHeap<'a, T> {
+    fn new() -> Self {
+        PfLazyPairingHeap::Empty
+    }
+
+    fn is_empty(&self) -> bool {
+        matches!(*self, PfLazyPairingHeap::Empty)
+    }
+
+    fn insert(&self, v: T) -> Self {
+        PfLazyPairingHeap::Node {
+            value: v,
+            odd: Ptr::new(PfLazyPairingHeap::Empty),
+            rest: lazy!(PfLazyPairingHeap::Empty)
+        }.merge(self)
+    }
+
+    fn merge(&self, h: &Self) -> Self {
+        use self::PfLazyPairingHeap::*;
+        match (self, h) {
+            (&Empty, h) => h.clone(),
+            (h, &Empty) => h.clone(),
+            (Node { value: v1, .. }, Node { value: v2, .. }) => {
+                if v1 <= v2 {
+                    self.link(h)
+                } else {
+                    h.link(self)
+                }
+            }
+        }
+    }
+
+    fn find_min(&self) -> Result<T, &str> {
+        match *self {
+            PfLazyPairingHeap::Empty =>
+                Err("find_min for empty lazy pairing heap"),
+            PfLazyPairingHeap::Node { ref value, .. } => Ok(value.clone())
+        }
+    }
+
+    fn delete_min(&self) -> Result<Self, &str> {
+        match *self {
+            PfLazyPairingHeap::Empty =>
+                Err("delete_min for empty lazy pairing heap"),
+            PfLazyPairingHeap::Node { ref odd, ref rest, .. } =>
+                Ok(odd.merge(rest.force()))
+        }
+    }
+}
+
+// 7.3 Scheduled binomial heap
+#[derive(Clone, Debug)]
+enum Digit<T: Ord + Clone + Display + Debug> {
+    Zero,
+    One(Ptr<BinomialTree<T>>)
+}
+
+type Digits<'a, T> = Stream<'a, Digit<T>>;
+
+#[derive(Clone, Debug)]
+pub struct PfScheduledBinomialHeap<'a, T: 'a + Ord + Clone + Display + Debug> {
+    digits: Digits<'a, T>,
+    schedule: PfList<Digits<'a, T>>
+}
+
+impl<'a, T: 'a + Ord + Clone + Display + Debug + Shareable>
+    PfScheduledBinomialHeap<'a, T> {
+    fn ins_tree(t: Ptr<BinomialTree<T>>, ds: Digits<'a, T>) -> Digits<'a, T> {
+        Stream::new(lazy!(
+            match ds.pop() {
+                Ok((Digit::One(t2), ds2)) => {
+                    let t = PfBinomialHeap::link(&t, &t2);
+                    Self::ins_tree(t, ds2).push(Digit::Zero).eval()
+                }
+                Ok((Digit::Zero, ds2)) =>
+                    ds2.push(Digit::One(t.clone())).eval(),
+                Err(_) => Stream::empty().push(Digit::One(t.clone())).eval()
+            }
+        ))
+    }
+
+    fn mrg(ds1: Digits<'a, T>, ds2: Digits<'a, T>) -> Digits<'a, T> {
+        Stream::new(lazy!(
+            match (ds1.pop(), ds2.pop()) {
+                (Err(_), _) => ds2.eval(),
+                (_, Err(_)) => ds1.eval(),
+                (Ok((Digit::One(t1), r1)), Ok((Digit::One(t2), r2))) => {
+                    let t = PfBinomialHeap::link(&t1, &t2);
+                    Self::ins_tree(t, Self::mrg(r1, r2))
+                        .push(Digit::Zero).eval()
+                }
+                (Ok((Digit::Zero, r1)), Ok((d, r2))) |
+                (Ok((d, r1)), Ok((Digit::Zero, r2))) =>
+                    Self::mrg(r1, r2).push(d).eval()
+            }
+        ))
+    }
+
+    fn normalize(ds: Digits<'a, T>) -> Digits<'a, T> {
+        for _ in ds.iter() {}
+        ds
+    }
+
+    fn exec(schedule: PfList<Digits<'a, T>>) -> PfList<Digits<'a, T>> {
+        match schedule.pop() {
+            Ok((ds, rest)) => match ds.pop() {
+                Ok((Digit::Zero, job)) => rest.push(job),
+                _ => rest
+            },
+            Err(_) => schedule
+        }
+    }
+
+    fn remove_min_tree(ds: &Digits<'a, T>)
+                       -> Option<(Ptr<BinomialTree<T>>, Digits<'a, T>)> {
+        let (d, rest) = ds.pop().ok()?;
+        match d {
+            Digit::Zero => {
+                let (t, r) = Self::remove_min_tree(&rest)?;
+                Some((t, r.push(Digit::Zero)))
+            }
+            Digit::One(t) => match Self::remove_min_tree(&rest) {
+                Some((t2, r2)) => {
+                    if t.value <= t2.value {
+                        Some((t, rest.push(Digit::Zero)))
+                    } else {
+                        Some((t2, r2.push(Digit::One(t))))
+                    }
+                }
+                None => Some((t, Stream::empty()))
+            }
+        }
+    }
+}
+
+impl<'a, T: 'a + Ord + Clone + Display + Debug + Shareable> PfHeap<T>
+    for PfScheduledBinomialHeap<'a, T> {
+    fn new() -> Self {
+        PfScheduledBinomialHeap {
+            digits: Stream::empty(),
+            schedule: PfList::new()
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.digits.head().is_err()
+    }
+
+    fn insert(&self, v: T) -> Self {
+        let t = Ptr::new(BinomialTree {
+            rank: 0,
+            value: v,
+            children: PfList::new()
+        });
+        let digits = Self::ins_tree(t, self.digits.clone());
+        let schedule = self.schedule.push(digits.clone());
+        PfScheduledBinomialHeap {
+            digits,
+            schedule: Self::exec(Self::exec(schedule))
+        }
+    }
+
+    fn merge(&self, h: &Self) -> Self {
+        PfScheduledBinomialHeap {
+            digits: Self::normalize(Self::mrg(self.digits.clone(),
+                                              h.digits.clone())),
+            schedule: PfList::new()
+        }
+    }
+
+    fn find_min(&self) -> Result<T, &str> {
+        match Self::remove_min_tree(&self.digits) {
+            Some((t, _)) => Ok(t.value.clone()),
+            None => Err("find_min for empty scheduled binomial heap")
+        }
+    }
+
+    fn delete_min(&self) -> Result<Self, &str> {
+        match Self::remove_min_tree(&self.digits) {
+            Some((t, ds)) => {
+                let mut cs = Stream::empty();
+                let mut c = t.children.clone();
+                while let Ok((t, nc)) = c.pop() {
+                    cs = cs.push(Digit::One(t));
+                    c = nc;
+                }
+                Ok(PfScheduledBinomialHeap {
+                    digits: Self::normalize(Self::mrg(cs, ds)),
+                    schedule: PfList::new()
+                })
+            }
+            None => Err("delete_min for empty scheduled binomial heap")
+        }
+    }
+}
+
+// 9.3.2 Skew binomial heap
+#[derive(Clone, Debug)]
+struct SkewBinomialTree<T: Ord + Clone + Display + Debug> {
+    rank: i32,
+    value: T,
+    extras: PfList<T>,
+    children: SkewBinomialTrees<T>
+}
+
+type SkewBinomialTrees<T> = PfList<Ptr<SkewBinomialTree<T>>>;
+
+#[derive(Clone, Debug)]
+pub struct PfSkewBinomialHeap<T: Ord + Clone + Display + Debug> {
+    trees: SkewBinomialTrees<T>
+}
+
+impl<T: Ord + Clone + Display + Debug> PfSkewBinomialHeap<T> {
+    fn link(t1: &Ptr<SkewBinomialTree<T>>, t2: &Ptr<SkewBinomialTree<T>>)
+            -> SkewBinomialTree<T> {
+        if t1.value <= t2.value {
+            SkewBinomialTree {
+                rank: t1.rank + 1,
+                value: t1.value.clone(),
+                extras: t1.extras.clone(),
+                children: t1.children.push(t2.clone())
+            }
+        } else {
+            SkewBinomialTree {
+                rank: t2.rank + 1,
+                value: t2.value.clone(),
+                extras: t2.extras.clone(),
+                children: t2.children.push(t1.clone())
+            }
+        }
+    }
+
+    fn skew_link(v: T, t1: &Ptr<SkewBinomialTree<T>>,
+                 t2: &Ptr<SkewBinomialTree<T>>) -> Ptr<SkewBinomialTree<T>> {
+        let t = Self::link(t1, t2);
+        if v <= t.value {
+            Ptr::new(SkewBinomialTree {
+                extras: t.extras.push(t.value),
+                value: v,
+                ..t
+            })
+        } else {
+            Ptr::new(SkewBinomialTree {
+                extras: t.extras.push(v),
+                ..t
+            })
+        }
+    }
+
+    fn ins_tree(t: Ptr<SkewBinomialTree<T>>, ts: &SkewBinomialTrees<T>)
+                -> SkewBinomialTrees<T> {
+        match ts.pop() {
+            Ok((t2, ts2)) => {
+                if t.rank < t2.rank {
+                    ts.push(t)
+                } else {
+                    Self::ins_tree(Ptr::new(Self::link(&t, &t2)), &ts2)
+                }
+            }
+            Err(_) => ts.push(t)
+        }
+    }
+
+    fn merge_trees(ts1: &SkewBinomialTrees<T>, ts2: &SkewBinomialTrees<T>)
+                   -> SkewBinomialTrees<T> {
+        match (ts1.pop(), ts2.pop()) {
+            (Err(_), _) => ts2.clone(),
+            (_, Err(_)) => ts1.clone(),
+            (Ok((t1, ts1d)), Ok((t2, ts2d))) => {
+                if t1.rank < t2.rank {
+                    Self::merge_trees(&ts1d, ts2).push(t1)
+                } else if t2.rank < t1.rank {
+                    Self::merge_trees(ts1, &ts2d).push(t2)
+                } else {
+                    Self::ins_tree(Ptr::new(Self::link(&t1, &t2)),
+                                   &Self::merge_trees(&ts1d, &ts2d))
+                }
+            }
+        }
+    }
+
+    fn normalize(ts: &SkewBinomialTrees<T>) -> SkewBinomialTrees<T> {
+        match ts.pop() {
+            Ok((t, ts)) => Self::ins_tree(t, &ts),
+            Err(_) => ts.clone()
+        }
+    }
+
+    fn remove_min_tree(ts: &SkewBinomialTrees<T>)
+                       -> Option<(Ptr<SkewBinomialTree<T>>,
+                                  SkewBinomialTrees<T>)> {
+        let (t, tsd) = ts.pop().ok()?;
+        match Self::remove_min_tree(&tsd) {
+            Some((t2, ts2)) => {
+                if t.value <= t2.value {
+                    Some((t, tsd))
+                } else {
+                    Some((t2, ts2.push(t)))
+                }
+            }
+            None => Some((t, tsd))
+        }
+    }
+}
+
+impl<T: Ord + Clone + Display + Debug> PfHeap<T> for PfSkewBinomialHeap<T> {
+    fn new() -> Self {
+        PfSkewBinomialHeap { trees: PfList::new() }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.trees.is_empty()
+    }
+
+    fn insert(&self, v: T) -> Self {
+        if let Ok((t1, ts)) = self.trees.pop() {
+            if let Ok((t2, ts)) = ts.pop() {
+                if t1.rank == t2.rank {
+                    let t = Self::skew_link(v, &t1, &t2);
+                    return PfSkewBinomialHeap { trees: ts.push(t) };
+                }
+            }
+        }
+        let t = Ptr::new(SkewBinomialTree {
+            rank: 0,
+            value: v,
+            extras: PfList::new(),
+            children: PfList::new()
+        });
+        PfSkewBinomialHeap { trees: self.trees.push(t) }
+    }
+
+    fn merge(&self, h: &Self) -> Self {
+        PfSkewBinomialHeap {
+            trees: Self::merge_trees(&Self::normalize(&self.trees),
+                                     &Self::normalize(&h.trees))
+        }
+    }
+
+    fn find_min(&self) -> Result<T, &str> {
+        match Self::remove_min_tree(&self.trees) {
+            Some((t, _)) => Ok(t.value.clone()),
+            None => Err("find_min for empty skew binomial heap")
+        }
+    }
+
+    fn delete_min(&self) -> Result<Self, &str> {
+        match Self::remove_min_tree(&self.trees) {
+            Some((t, ts)) => {
+                let mut h = PfSkewBinomialHeap { trees: t.children.rev() }
+                    .merge(&PfSkewBinomialHeap { trees: ts });
+                let mut xs = t.extras.clone();
+                while let Ok((x, nxs)) = xs.pop() {
+                    h = h.insert(x);
+                    xs = nxs;
+                }
+                Ok(h)
+            }
+            None => Err("delete_min for empty skew binomial heap")
+        }
+    }
+}
+
+// 10.2.2 Bootstrapped heap
+pub trait PfHeapFamily {
+    type Elem: Ord + Clone + Display + Debug;
+    type Rebind<E: Ord + Clone + Display + Debug>: PfHeap<E> + Debug;
+}
+
+impl<T: Ord + Clone + Display + Debug> PfHeapFamily for PfLeftistHeap<T> {
+    type Elem = T;
+    type Rebind<E: Ord + Clone + Display + Debug> = PfLeftistHeap<E>;
+}
+
+impl<T: Ord + Clone + Display + Debug> PfHeapFamily for PfBinomialHeap<T> {
+    type Elem = T;
+    type Rebind<E: Ord + Clone + Display + Debug> = PfBinomialHeap<E>;
+}
+
+impl<T: Ord + Clone + Display + Debug> PfHeapFamily for PfSplayHeap<T> {
+    type Elem = T;
+    type Rebind<E: Ord + Clone + Display + Debug> = PfSplayHeap<E>;
+}
+
+impl<T: Ord + Clone + Display + Debug> PfHeapFamily for PfPairingHeap<T> {
+    type Elem = T;
+    type Rebind<E: Ord + Clone + Display + Debug> = PfPairingHeap<E>;
+}
+
+impl<T: Ord + Clone + Display + Debug> PfHeapFamily
+    for PfSkewBinomialHeap<T> {
+    type Elem = T;
+    type Rebind<E: Ord + Clone + Display + Debug> = PfSkewBinomialHeap<E>;
+}
+
+pub enum PfBootstrappedHeap<H: PfHeapFamily> {
+    Empty,
+    Node {
+        value: H::Elem,
+        heaps: Ptr<H::Rebind<PfBootstrappedHeap<H>>>
+    }
+}
+
+impl<H: PfHeapFamily> Clone for PfBootstrappedHeap<H> {
+    fn clone(&self) -> Self {
+        match *self {
+            PfBootstrappedHeap::Empty => PfBootstrappedHeap::Empty,
+            PfBootstrappedHeap::Node {
+                ref value, ref heaps
+            } => PfBootstrappedHeap::Node {
+                value: value.clone(),
+                heaps: heaps.clone()
+            }
+        }
+    }
+}
+
+impl<H: PfHeapFamily> Debug for PfBootstrappedHeap<H> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            PfBootstrappedHeap::Empty => f.write_str("Empty"),
+            PfBootstrappedHeap::Node { ref value, ref heaps } =>
+                f.debug_struct("Node")
+                    .field("value", value)
+                    .field("heaps", heaps)
+                    .finish()
+        }
+    }
+}
+
+impl<H: PfHeapFamily> Display for PfBootstrappedHeap<H> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            PfBootstrappedHeap::Empty => f.write_str("Empty"),
+            PfBootstrappedHeap::Node { ref value, .. } =>
+                f.write_fmt(format_args!("Node({})", value))
+        }
+    }
+}
+
+// Bootstrapped heaps are ordered by their minimum elements.
+impl<H: PfHeapFamily> Ord for PfBootstrappedHeap<H> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        use self::PfBootstrappedHeap::*;
+        match (self, other) {
+            (&Empty, &Empty) => Ordering::Equal,
+            (&Empty, _) => Ordering::Less,
+            (_, &Empty) => Ordering::Greater,
+            (Node { value: x, .. }, Node { value: y, .. }) => x.cmp(y)
+        }
+    }
+}
+
+impl<H: PfHeapFamily> PartialOrd for PfBootstrappedHeap<H> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<H: PfHeapFamily> PartialEq for PfBootstrappedHeap<H> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<H: PfHeapFamily> Eq for PfBootstrappedHeap<H> {}
+
+impl<H: PfHeapFamily> PfHeap<H::Elem> for PfBootstrappedHeap<H> {
+    fn new() -> Self {
+        PfBootstrappedHeap::Empty
+    }
+
+    fn is_empty(&self) -> bool {
+        matches!(*self, PfBootstrappedHeap::Empty)
+    }
+
+    fn insert(&self, v: H::Elem) -> Self {
+        PfBootstrappedHeap::Node {
+            value: v,
+            heaps: Ptr::new(H::Rebind::new())
+        }.merge(self)
+    }
+
+    fn merge(&self, h: &Self) -> Self {
+        use self::PfBootstrappedHeap::*;
+        match (self, h) {
+            (&Empty, h) => h.clone(),
+            (h, &Empty) => h.clone(),
+            (Node { value: x, heaps: p1 }, Node { value: y, heaps: p2 }) => {
+                if x <= y {
+                    Node {
+                        value: x.clone(),
+                        heaps: Ptr::new(p1.insert(h.clone()))
+                    }
+                } else {
+                    Node {
+                        value: y.clone(),
+                        heaps: Ptr::new(p2.insert(self.clone()))
+                    }
+                }
+            }
+        }
+    }
+
+    fn find_min(&self) -> Result<H::Elem, &str> {
+        match *self {
+            PfBootstrappedHeap::Empty =>
+                Err("find_min for empty bootstrapped heap"),
+            PfBootstrappedHeap::Node { ref value, .. } => Ok(value.clone())
+        }
+    }
+
+    fn delete_min(&self) -> Result<Self, &str> {
+        use self::PfBootstrappedHeap::*;
+        match *self {
+            Empty => Err("delete_min for empty bootstrapped heap"),
+            Node { ref heaps, .. } => match heaps.find_min() {
+                Ok(Node { value: y, heaps: p1 }) => {
+                    let p2 = heaps.delete_min().unwrap();
+                    Ok(Node { value: y, heaps: Ptr::new(p1.merge(&p2)) })
+                }
+                Ok(Empty) =>
+                    unreachable!("empty heap inside bootstrapped heap"),
+                Err(_) => Ok(Empty)
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -256,13 +1150,72 @@ mod tests {
         assert_eq!(42, h6.find_min().unwrap());
     }
 
+    fn test_pf_heap_sort<Heap: PfHeap<i32>>() {
+        assert_eq!(Vec::<i32>::new(), Heap::new().into_sorted_vec());
+        assert_eq!(vec![1, 1, 2, 3, 4, 5, 6, 9],
+                   heap_sort::<Heap, _>(vec![3, 1, 4, 1, 5, 9, 2, 6]));
+
+        let h1 = Heap::from_iter(vec![7, 3, 5]);
+        let h2 = Heap::from_iter(vec![6, 2, 8, 4]);
+        let h3 = h1.merge(&h2);
+        assert_eq!(2, h3.find_min().unwrap());
+        assert_eq!(vec![2, 3, 4, 5, 6, 7, 8], h3.into_sorted_vec());
+        assert_eq!(vec![3, 5, 7], h1.into_sorted_vec());
+    }
+
     #[test]
     fn test_pf_leftish_heap() {
         test_pf_heap::<PfLeftistHeap<i32>>();
     }
 
+    #[test]
+    fn test_pf_binomial_heap() {
+        test_pf_heap::<PfBinomialHeap<i32>>();
+    }
+
+    #[test]
+    fn test_pf_splay_heap() {
+        test_pf_heap::<PfSplayHeap<i32>>();
+    }
+
     #[test]
     fn test_pf_pairing_heap() {
         test_pf_heap::<PfPairingHeap<i32>>();
     }
+
+    #[test]
+    fn test_pf_lazy_pairing_heap() {
+        test_pf_heap::<PfLazyPairingHeap<i32>>();
+    }
+
+    #[test]
+    fn test_pf_scheduled_binomial_heap() {
+        test_pf_heap::<PfScheduledBinomialHeap<i32>>();
+    }
+
+    #[test]
+    fn test_pf_skew_binomial_heap() {
+        test_pf_heap::<PfSkewBinomialHeap<i32>>();
+    }
+
+    #[test]
+    fn test_pf_bootstrapped_heap() {
+        test_pf_heap::<PfBootstrappedHeap<PfLeftistHeap<i32>>>();
+        test_pf_heap::<PfBootstrappedHeap<PfPairingHeap<i32>>>();
+        test_pf_heap::<PfBootstrappedHeap<PfSkewBinomialHeap<i32>>>();
+    }
+
+    #[test]
+    fn test_heap_sort() {
+        test_pf_heap_sort::<PfLeftistHeap<i32>>();
+        test_pf_heap_sort::<PfBinomialHeap<i32>>();
+        test_pf_heap_sort::<PfSplayHeap<i32>>();
+        test_pf_heap_sort::<PfPairingHeap<i32>>();
+        test_pf_heap_sort::<PfLazyPairingHeap<i32>>();
+        test_pf_heap_sort::<PfScheduledBinomialHeap<i32>>();
+        test_pf_heap_sort::<PfSkewBinomialHeap<i32>>();
+        test_pf_heap_sort::<PfBootstrappedHeap<PfLeftistHeap<i32>>>();
+        test_pf_heap_sort::<PfBootstrappedHeap<PfPairingHeap<i32>>>();
+        test_pf_heap_sort::<PfBootstrappedHeap<PfSkewBinomialHeap<i32>>>();
+    }
 }
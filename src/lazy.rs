@@ -1,47 +1,262 @@
 use std::boxed::Box;
-use std::cell::RefCell;
+#[cfg(not(feature = "sync"))]
+use std::cell::{OnceCell, RefCell};
 use std::fmt;
 use std::fmt::Debug;
+use std::mem;
+#[cfg(not(feature = "sync"))]
+use std::panic;
+#[cfg(not(feature = "sync"))]
+use std::panic::AssertUnwindSafe;
+#[cfg(not(feature = "sync"))]
 use std::rc::Rc;
+use std::sync::{Arc, Condvar, Mutex, OnceLock};
+use std::thread;
+use std::thread::ThreadId;
 
+// Everything captured by a thunk must be shareable across threads when
+// the `sync` feature is on; otherwise this bound holds for every type.
+#[cfg(not(feature = "sync"))]
+pub trait Shareable {}
+#[cfg(not(feature = "sync"))]
+impl<T: ?Sized> Shareable for T {}
+
+#[cfg(feature = "sync")]
+pub trait Shareable: Send + Sync {}
+#[cfg(feature = "sync")]
+impl<T: ?Sized + Send + Sync> Shareable for T {}
+
+// A thunk drops its closure, and everything the closure captured, once
+// it has produced the value. The value sits in a once-cell so that it
+// can be borrowed; once it is set the state is no longer consulted.
+#[cfg(not(feature = "sync"))]
+enum State<'a, T> {
+    Pending(Box<dyn FnOnce() -> T + 'a>),
+    Forcing,
+    Poisoned,
+}
+
+#[cfg(not(feature = "sync"))]
 struct Impl<'a, T: 'a + Clone + Debug> {
-    thunk: Box<Fn() -> T + 'a>,
-    value: Option<T>,
+    state: RefCell<State<'a, T>>,
+    value: OnceCell<T>,
 }
 
+#[cfg(not(feature = "sync"))]
 #[derive(Clone, Debug)]
 pub struct Thunk<'a, T: 'a + Clone + Debug> {
-    imp: Rc<RefCell<Impl<'a, T>>>
+    imp: Rc<Impl<'a, T>>
 }
 
+#[cfg(not(feature = "sync"))]
 impl<'a, T: 'a + Clone + Debug> Debug for Impl<'a, T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        match self.value {
-            Some(ref v) => f.write_fmt(format_args!("Impl({:?})", v)),
-            None => f.write_str("Impl(*todo*)"),
+        if let Some(v) = self.value.get() {
+            return f.write_fmt(format_args!("Impl({:?})", v));
+        }
+        match *self.state.borrow() {
+            State::Poisoned => f.write_str("Impl(*poisoned*)"),
+            _ => f.write_str("Impl(*todo*)"),
         }
     }
 }
 
+#[cfg(not(feature = "sync"))]
 impl<'a, T: Clone + Debug> Thunk<'a, T> {
     pub fn new<F>(t: F) -> Self
-        where F: Fn() -> T + 'a {
+        where F: FnOnce() -> T + Shareable + 'a {
         Thunk {
-            imp: Rc::new(RefCell::new(Impl {
-                thunk: Box::new(t),
-                value: None
-            }))
+            imp: Rc::new(Impl {
+                state: RefCell::new(State::Pending(Box::new(t))),
+                value: OnceCell::new()
+            })
+        }
+    }
+
+    // Borrows the value, computing it on first use. Panics if the
+    // thunk's closure panicked or forced the thunk itself; use
+    // `try_force` to get either back as an error.
+    pub fn force(&self) -> &T {
+        match self.try_force() {
+            Ok(value) => value,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_force(&self) -> Result<&T, &str> {
+        if let Some(v) = self.imp.value.get() {
+            return Ok(v);
+        }
+        let thunk = match self.imp.state.replace(State::Forcing) {
+            State::Pending(thunk) => thunk,
+            State::Forcing => return Err("cycle in thunk evaluation"),
+            State::Poisoned => {
+                *self.imp.state.borrow_mut() = State::Poisoned;
+                return Err("thunk evaluation panicked");
+            }
+        };
+
+        // A closure that unwinds poisons the thunk, so that later forces
+        // do not mistake it for one still being evaluated.
+        match panic::catch_unwind(AssertUnwindSafe(thunk)) {
+            Ok(value) => Ok(self.imp.value.get_or_init(|| value)),
+            Err(payload) => {
+                *self.imp.state.borrow_mut() = State::Poisoned;
+                panic::resume_unwind(payload)
+            }
         }
     }
 
     pub fn eval(&self) -> T {
-        let mut imp = self.imp.borrow_mut();
-        let value = match imp.value {
-            Some(ref value) => return value.clone(),
-            None => (imp.thunk)()
+        self.force().clone()
+    }
+
+    pub fn try_eval(&self) -> Result<T, &str> {
+        self.try_force().cloned()
+    }
+}
+
+// With the `sync` feature, concurrent callers block until the first one
+// has computed the value.
+#[cfg(feature = "sync")]
+#[derive(Clone, Debug)]
+pub struct Thunk<'a, T: 'a + Clone + Debug> {
+    imp: SyncThunk<'a, T>
+}
+
+#[cfg(feature = "sync")]
+impl<'a, T: Clone + Debug> Thunk<'a, T> {
+    pub fn new<F>(t: F) -> Self
+        where F: FnOnce() -> T + Shareable + 'a {
+        Thunk { imp: SyncThunk::new(t) }
+    }
+
+    // Borrows the value, computing it on first use. Panics if the
+    // thunk's closure panicked or forced the thunk itself; use
+    // `try_force` to get either back as an error.
+    pub fn force(&self) -> &T {
+        match self.imp.force() {
+            Ok(value) => value,
+            Err(e) => panic!("{}", e)
+        }
+    }
+
+    pub fn try_force(&self) -> Result<&T, &str> {
+        self.imp.force()
+    }
+
+    pub fn eval(&self) -> T {
+        self.force().clone()
+    }
+
+    pub fn try_eval(&self) -> Result<T, &str> {
+        self.imp.eval()
+    }
+}
+
+// A thunk that can be forced from many threads. The first forcer runs
+// the closure while the others wait for its result, and the closure is
+// dropped as soon as it has run. Forcing a thunk from inside its own
+// closure is reported as an error rather than deadlocking; cycles that
+// span several threads are not detected.
+enum SyncState<'a, T> {
+    Pending(Box<dyn FnOnce() -> T + Send + 'a>),
+    Forcing(ThreadId),
+    Done,
+    Poisoned,
+}
+
+// The value lives outside the mutex so that it can be borrowed once set.
+struct SyncImpl<'a, T> {
+    state: Mutex<SyncState<'a, T>>,
+    forced: Condvar,
+    value: OnceLock<T>,
+}
+
+#[derive(Clone)]
+pub struct SyncThunk<'a, T: 'a + Clone + Debug> {
+    imp: Arc<SyncImpl<'a, T>>
+}
+
+// Marks the thunk as poisoned if its closure unwinds, so that threads
+// waiting for it wake up instead of blocking forever.
+struct PoisonOnUnwind<'b, 'a: 'b, T: 'a> {
+    imp: &'b SyncImpl<'a, T>
+}
+
+impl<'b, 'a: 'b, T: 'a> Drop for PoisonOnUnwind<'b, 'a, T> {
+    fn drop(&mut self) {
+        if thread::panicking() {
+            if let Ok(mut state) = self.imp.state.lock() {
+                *state = SyncState::Poisoned;
+            }
+            self.imp.forced.notify_all();
+        }
+    }
+}
+
+impl<'a, T: 'a + Clone + Debug> Debug for SyncThunk<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        if let Some(v) = self.imp.value.get() {
+            return f.write_fmt(format_args!("SyncThunk({:?})", v));
+        }
+        match self.imp.state.lock() {
+            Ok(ref state) => match **state {
+                SyncState::Poisoned => f.write_str("SyncThunk(*poisoned*)"),
+                _ => f.write_str("SyncThunk(*todo*)"),
+            },
+            Err(_) => f.write_str("SyncThunk(*poisoned*)"),
+        }
+    }
+}
+
+impl<'a, T: 'a + Clone + Debug> SyncThunk<'a, T> {
+    pub fn new<F>(t: F) -> Self
+        where F: FnOnce() -> T + Send + 'a {
+        SyncThunk {
+            imp: Arc::new(SyncImpl {
+                state: Mutex::new(SyncState::Pending(Box::new(t))),
+                forced: Condvar::new(),
+                value: OnceLock::new()
+            })
+        }
+    }
+
+    pub fn force(&self) -> Result<&T, &str> {
+        if let Some(v) = self.imp.value.get() {
+            return Ok(v);
+        }
+        let me = thread::current().id();
+        let mut state = self.imp.state.lock().unwrap();
+        loop {
+            match *state {
+                SyncState::Done => return Ok(self.imp.value.get().unwrap()),
+                SyncState::Poisoned => return Err("thunk evaluation panicked"),
+                SyncState::Forcing(id) if id == me =>
+                    return Err("cycle in thunk evaluation"),
+                SyncState::Forcing(_) =>
+                    state = self.imp.forced.wait(state).unwrap(),
+                SyncState::Pending(_) => break
+            }
+        }
+        let thunk = match mem::replace(&mut *state, SyncState::Forcing(me)) {
+            SyncState::Pending(thunk) => thunk,
+            _ => unreachable!()
         };
-        imp.value = Some(value.clone());
-        value
+        drop(state);
+
+        let value = {
+            let _guard = PoisonOnUnwind { imp: &self.imp };
+            thunk()
+        };
+        self.imp.value.set(value).unwrap();
+        *self.imp.state.lock().unwrap() = SyncState::Done;
+        self.imp.forced.notify_all();
+        Ok(self.imp.value.get().unwrap())
+    }
+
+    pub fn eval(&self) -> Result<T, &str> {
+        self.force().cloned()
     }
 }
 
@@ -53,6 +268,7 @@ macro_rules! lazy {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use Ptr;
 
     static mut V: i32 = 7;
     fn inc() -> i32 {
@@ -63,6 +279,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(static_mut_refs)]
     fn test_lazy() {
         let result = lazy!(2 + 3);
         assert_eq!(5, result.eval());
@@ -75,4 +292,137 @@ mod tests {
         assert_eq!(8, result.eval());
         unsafe { assert_eq!(8, V); }
     }
+
+    #[test]
+    fn test_lazy_releases_closure() {
+        let v = Ptr::new(5);
+        let c = v.clone();
+        let result = lazy!(*c + 1);
+        assert_eq!(2, Ptr::strong_count(&v));
+        assert_eq!(6, result.eval());
+        assert_eq!(1, Ptr::strong_count(&v));
+        assert_eq!(6, result.eval());
+    }
+
+    #[test]
+    fn test_lazy_panic() {
+        use std::panic;
+        use std::panic::AssertUnwindSafe;
+
+        let result: Thunk<i32> = lazy!(panic!("boom"));
+        assert!(panic::catch_unwind(AssertUnwindSafe(|| result.eval()))
+                .is_err());
+        assert_eq!(Err("thunk evaluation panicked"), result.try_eval());
+    }
+
+    #[test]
+    fn test_lazy_once() {
+        let v = vec![1, 2, 3];
+        let result = lazy!(v.into_iter().sum::<i32>());
+        assert_eq!(6, result.eval());
+        assert_eq!(6, result.eval());
+    }
+
+    #[test]
+    fn test_lazy_force() {
+        let result = lazy!(vec![1, 2, 3]);
+        assert_eq!(&vec![1, 2, 3], result.force());
+        assert!(std::ptr::eq(result.force(), result.force()));
+        let r = result.clone();
+        assert!(std::ptr::eq(result.force(), r.force()));
+    }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_lazy_sync() {
+        use std::sync::atomic::AtomicUsize;
+        use std::sync::atomic::Ordering;
+        use std::thread;
+        use std::time::Duration;
+
+        let count = Arc::new(AtomicUsize::new(0));
+        let c = count.clone();
+        let result = lazy!({
+            c.fetch_add(1, Ordering::SeqCst);
+            thread::sleep(Duration::from_millis(10));
+            42
+        });
+        let handles: Vec<_> = (0..8).map(|_| {
+            let r = result.clone();
+            thread::spawn(move || r.eval())
+        }).collect();
+        for h in handles {
+            assert_eq!(42, h.join().unwrap());
+        }
+        assert_eq!(1, count.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_lazy_cycle() {
+        let me: Ptr<Mutex<Option<Thunk<i32>>>> = Ptr::new(Mutex::new(None));
+        let m = me.clone();
+        let result = lazy!({
+            let t = m.lock().unwrap().clone().unwrap();
+            match t.try_eval() {
+                Ok(v) => v + 1,
+                Err(_) => -1
+            }
+        });
+        *me.lock().unwrap() = Some(result.clone());
+        assert_eq!(Ok(-1), result.try_eval());
+        assert_eq!(-1, result.eval());
+    }
+
+    #[test]
+    fn test_sync_thunk() {
+        use std::sync::atomic::AtomicUsize;
+        use std::sync::atomic::Ordering;
+        use std::time::Duration;
+
+        let count = Arc::new(AtomicUsize::new(0));
+        let c = count.clone();
+        let result = SyncThunk::new(move || {
+            c.fetch_add(1, Ordering::SeqCst);
+            thread::sleep(Duration::from_millis(10));
+            42
+        });
+        assert_eq!(2, Arc::strong_count(&count));
+        let handles: Vec<_> = (0..8).map(|_| {
+            let r = result.clone();
+            thread::spawn(move || r.eval().unwrap())
+        }).collect();
+        for h in handles {
+            assert_eq!(42, h.join().unwrap());
+        }
+        assert_eq!(Ok(42), result.eval());
+        assert_eq!(Ok(&42), result.force());
+        assert_eq!(1, count.load(Ordering::SeqCst));
+        // The closure, and the counter it captured, are gone.
+        assert_eq!(1, Arc::strong_count(&count));
+    }
+
+    #[test]
+    fn test_sync_thunk_cycle() {
+        let me: Arc<Mutex<Option<SyncThunk<i32>>>> =
+            Arc::new(Mutex::new(None));
+        let m = me.clone();
+        let result = SyncThunk::new(move || {
+            let t = m.lock().unwrap().clone().unwrap();
+            match t.eval() {
+                Ok(v) => v + 1,
+                Err(_) => -1
+            }
+        });
+        *me.lock().unwrap() = Some(result.clone());
+        assert_eq!(Ok(-1), result.eval());
+        assert_eq!(Ok(-1), me.lock().unwrap().clone().unwrap().eval());
+    }
+
+    #[test]
+    fn test_sync_thunk_panic() {
+        let result = SyncThunk::<i32>::new(|| panic!("boom"));
+        let r = result.clone();
+        assert!(thread::spawn(move || r.eval().ok()).join().is_err());
+        assert!(result.eval().is_err());
+    }
 }
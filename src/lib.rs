@@ -1,6 +1,16 @@
+// Shared pointer behind every structure; `Arc` with the `sync` feature.
+#[cfg(not(feature = "sync"))]
+use std::rc::Rc as Ptr;
+#[cfg(feature = "sync")]
+use std::sync::Arc as Ptr;
+
 #[macro_use]
 pub mod lazy;
 pub mod stream;
 pub mod list;
 pub mod queue;
 pub mod heap;
+pub mod rbtree;
+pub mod catenable;
+pub mod trie;
+pub mod sortable;
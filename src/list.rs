@@ -1,42 +1,56 @@
 use std::fmt::Debug;
-use std::rc::Rc;
+use std::marker::PhantomData;
+
+use Ptr;
+use lazy::Shareable;
 
 #[derive(Debug)]
 enum PfListNode<T: Clone + Debug> {
     Empty,
     Node {
         value: T,
-        next: Rc<PfListNode<T>>
+        next: Ptr<PfListNode<T>>
     }
 }
 
 #[derive(Clone, Debug)]
 pub struct PfList<T: Clone + Debug> {
-    head: Rc<PfListNode<T>>
+    head: Ptr<PfListNode<T>>
 }
 
 impl<T: Clone + Debug> PfList<T> {
     pub fn is_empty(&self) -> bool {
-        if let PfListNode::Empty = *self.head {
-            return true;
-        } else {
-            return false;
-        }
+        matches!(*self.head, PfListNode::Empty)
     }
 
     pub fn new() -> Self {
-        PfList { head: Rc::new(PfListNode::Empty) }
+        PfList { head: Ptr::new(PfListNode::Empty) }
     }
 
     pub fn push(&self, v: T) -> Self {
         PfList {
-            head: Rc::new(PfListNode::Node {
+            head: Ptr::new(PfListNode::Node {
                 value: v,
                 next: self.head.clone()
             })
         }
     }
 
+    pub fn head(&self) -> Result<&T, &str> {
+        match *self.head {
+            PfListNode::Empty => Err("head for empty list"),
+            PfListNode::Node { ref value, .. } => Ok(value)
+        }
+    }
+
+    pub fn tail(&self) -> Result<Self, &str> {
+        match *self.head {
+            PfListNode::Empty => Err("tail for empty list"),
+            PfListNode::Node { ref next, .. } =>
+                Ok(PfList { head: next.clone() })
+        }
+    }
+
     pub fn pop(&self) -> Result<(T, Self), &str> {
         match *self.head {
             PfListNode::Empty => Err("pop for empty list"),
@@ -45,6 +59,21 @@ impl<T: Clone + Debug> PfList<T> {
         }
     }
 
+    // Borrows the elements from front to back.
+    pub fn iter(&self) -> PfListIter<'_, T> {
+        PfListIter { node: &self.head }
+    }
+
+    pub fn append(&self, l: &Self) -> Self {
+        let mut ret = l.clone();
+        let mut cur = self.rev();
+        while let Ok((v, ncur)) = cur.pop() {
+            ret = ret.push(v);
+            cur = ncur;
+        }
+        ret
+    }
+
     pub fn rev(&self) -> Self {
         let mut ret = PfList::new();
         let mut cur = self.clone();
@@ -57,6 +86,613 @@ impl<T: Clone + Debug> PfList<T> {
     }
 }
 
+impl<T: Clone + Debug> Default for PfList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct PfListIter<'a, T: 'a + Clone + Debug> {
+    node: &'a PfListNode<T>
+}
+
+impl<'a, T: Clone + Debug> Iterator for PfListIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        match *self.node {
+            PfListNode::Empty => None,
+            PfListNode::Node { ref value, ref next } => {
+                self.node = next;
+                Some(value)
+            }
+        }
+    }
+}
+
+// 9.2.1 Binary random-access list
+#[derive(Debug)]
+enum BinaryTree<T: Clone + Debug> {
+    Leaf(T),
+    Node(usize, Ptr<BinaryTree<T>>, Ptr<BinaryTree<T>>)
+}
+
+#[derive(Clone, Debug)]
+enum BinaryDigit<T: Clone + Debug> {
+    Zero,
+    One(Ptr<BinaryTree<T>>)
+}
+
+type BinaryDigits<T> = PfList<BinaryDigit<T>>;
+
+impl<T: Clone + Debug> BinaryTree<T> {
+    fn size(&self) -> usize {
+        match *self {
+            BinaryTree::Leaf(_) => 1,
+            BinaryTree::Node(w, _, _) => w
+        }
+    }
+
+    fn link(t1: Ptr<Self>, t2: Ptr<Self>) -> Ptr<Self> {
+        Ptr::new(BinaryTree::Node(t1.size() + t2.size(), t1, t2))
+    }
+
+    fn lookup(&self, i: usize) -> &T {
+        match *self {
+            BinaryTree::Leaf(ref v) => v,
+            BinaryTree::Node(w, ref t1, ref t2) => {
+                if i < w / 2 {
+                    t1.lookup(i)
+                } else {
+                    t2.lookup(i - w / 2)
+                }
+            }
+        }
+    }
+
+    fn update(&self, i: usize, v: T) -> Self {
+        match *self {
+            BinaryTree::Leaf(_) => BinaryTree::Leaf(v),
+            BinaryTree::Node(w, ref t1, ref t2) => {
+                if i < w / 2 {
+                    BinaryTree::Node(w, Ptr::new(t1.update(i, v)), t2.clone())
+                } else {
+                    BinaryTree::Node(w, t1.clone(),
+                                     Ptr::new(t2.update(i - w / 2, v)))
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PfRandomAccessList<T: Clone + Debug> {
+    digits: BinaryDigits<T>
+}
+
+impl<T: Clone + Debug> PfRandomAccessList<T> {
+    fn cons_tree(t: Ptr<BinaryTree<T>>, ts: &BinaryDigits<T>)
+                 -> BinaryDigits<T> {
+        match ts.pop() {
+            Err(_) => ts.push(BinaryDigit::One(t)),
+            Ok((BinaryDigit::Zero, ts)) => ts.push(BinaryDigit::One(t)),
+            Ok((BinaryDigit::One(t2), ts)) =>
+                Self::cons_tree(BinaryTree::link(t, t2), &ts)
+                    .push(BinaryDigit::Zero)
+        }
+    }
+
+    fn uncons_tree(ts: &BinaryDigits<T>)
+                   -> Option<(Ptr<BinaryTree<T>>, BinaryDigits<T>)> {
+        let (d, ts) = ts.pop().ok()?;
+        match d {
+            BinaryDigit::One(t) => {
+                if ts.is_empty() {
+                    Some((t, ts))
+                } else {
+                    Some((t, ts.push(BinaryDigit::Zero)))
+                }
+            }
+            BinaryDigit::Zero => {
+                let (t, ts) = Self::uncons_tree(&ts)?;
+                match *t {
+                    BinaryTree::Node(_, ref t1, ref t2) => {
+                        let ts = ts.push(BinaryDigit::One(t2.clone()));
+                        Some((t1.clone(), ts))
+                    }
+                    BinaryTree::Leaf(_) => unreachable!()
+                }
+            }
+        }
+    }
+
+    fn update_digits(ts: &BinaryDigits<T>, i: usize, v: T)
+                     -> Option<BinaryDigits<T>> {
+        let (d, ts) = ts.pop().ok()?;
+        match d {
+            BinaryDigit::Zero =>
+                Some(Self::update_digits(&ts, i, v)?.push(BinaryDigit::Zero)),
+            BinaryDigit::One(t) => {
+                if i < t.size() {
+                    Some(ts.push(BinaryDigit::One(Ptr::new(t.update(i, v)))))
+                } else {
+                    Some(Self::update_digits(&ts, i - t.size(), v)?
+                         .push(BinaryDigit::One(t)))
+                }
+            }
+        }
+    }
+
+    pub fn new() -> Self {
+        PfRandomAccessList { digits: PfList::new() }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.digits.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        let mut n = 0;
+        for d in self.digits.iter() {
+            if let BinaryDigit::One(ref t) = *d {
+                n += t.size();
+            }
+        }
+        n
+    }
+
+    pub fn cons(&self, v: T) -> Self {
+        let t = Ptr::new(BinaryTree::Leaf(v));
+        PfRandomAccessList { digits: Self::cons_tree(t, &self.digits) }
+    }
+
+    pub fn head(&self) -> Result<T, &str> {
+        match Self::uncons_tree(&self.digits) {
+            Some((t, _)) => Ok(t.lookup(0).clone()),
+            None => Err("head for empty random access list")
+        }
+    }
+
+    pub fn tail(&self) -> Result<Self, &str> {
+        match Self::uncons_tree(&self.digits) {
+            Some((_, digits)) => Ok(PfRandomAccessList { digits }),
+            None => Err("tail for empty random access list")
+        }
+    }
+
+    pub fn lookup(&self, i: usize) -> Result<T, &str> {
+        let mut i = i;
+        for d in self.digits.iter() {
+            if let BinaryDigit::One(ref t) = *d {
+                if i < t.size() {
+                    return Ok(t.lookup(i).clone());
+                }
+                i -= t.size();
+            }
+        }
+        Err("lookup out of range for random access list")
+    }
+
+    pub fn update(&self, i: usize, v: T) -> Result<Self, &str> {
+        match Self::update_digits(&self.digits, i, v) {
+            Some(digits) => Ok(PfRandomAccessList { digits }),
+            None => Err("update out of range for random access list")
+        }
+    }
+}
+
+impl<T: Clone + Debug> Default for PfRandomAccessList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// 9.3.1 Skew binary random-access list
+#[derive(Debug)]
+enum SkewTree<T: Clone + Debug> {
+    Leaf(T),
+    Node(T, Ptr<SkewTree<T>>, Ptr<SkewTree<T>>)
+}
+
+impl<T: Clone + Debug> SkewTree<T> {
+    fn value(&self) -> &T {
+        match *self {
+            SkewTree::Leaf(ref v) => v,
+            SkewTree::Node(ref v, _, _) => v
+        }
+    }
+
+    fn lookup(&self, w: usize, i: usize) -> &T {
+        match *self {
+            SkewTree::Leaf(ref v) => v,
+            SkewTree::Node(ref v, ref t1, ref t2) => {
+                if i == 0 {
+                    v
+                } else if i <= w / 2 {
+                    t1.lookup(w / 2, i - 1)
+                } else {
+                    t2.lookup(w / 2, i - 1 - w / 2)
+                }
+            }
+        }
+    }
+
+    fn update(&self, w: usize, i: usize, v: T) -> Self {
+        match *self {
+            SkewTree::Leaf(_) => SkewTree::Leaf(v),
+            SkewTree::Node(ref x, ref t1, ref t2) => {
+                if i == 0 {
+                    SkewTree::Node(v, t1.clone(), t2.clone())
+                } else if i <= w / 2 {
+                    SkewTree::Node(x.clone(),
+                                   Ptr::new(t1.update(w / 2, i - 1, v)),
+                                   t2.clone())
+                } else {
+                    SkewTree::Node(x.clone(), t1.clone(),
+                                   Ptr::new(t2.update(w / 2, i - 1 - w / 2, v)))
+                }
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PfSkewList<T: Clone + Debug> {
+    trees: PfList<(usize, Ptr<SkewTree<T>>)>
+}
+
+impl<T: Clone + Debug> PfSkewList<T> {
+    fn update_trees(ts: &PfList<(usize, Ptr<SkewTree<T>>)>, i: usize, v: T)
+                    -> Option<PfList<(usize, Ptr<SkewTree<T>>)>> {
+        let ((w, t), ts) = ts.pop().ok()?;
+        if i < w {
+            Some(ts.push((w, Ptr::new(t.update(w, i, v)))))
+        } else {
+            Some(Self::update_trees(&ts, i - w, v)?.push((w, t)))
+        }
+    }
+
+    pub fn new() -> Self {
+        PfSkewList { trees: PfList::new() }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.trees.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.trees.iter().map(|&(w, _)| w).sum()
+    }
+
+    pub fn cons(&self, v: T) -> Self {
+        if let Ok(((w1, t1), ts)) = self.trees.pop() {
+            if let Ok(((w2, t2), ts)) = ts.pop() {
+                if w1 == w2 {
+                    let t = Ptr::new(SkewTree::Node(v, t1, t2));
+                    return PfSkewList { trees: ts.push((1 + w1 + w2, t)) };
+                }
+            }
+        }
+        PfSkewList {
+            trees: self.trees.push((1, Ptr::new(SkewTree::Leaf(v))))
+        }
+    }
+
+    pub fn head(&self) -> Result<T, &str> {
+        match self.trees.head() {
+            Ok((_, t)) => Ok(t.value().clone()),
+            Err(_) => Err("head for empty skew list")
+        }
+    }
+
+    pub fn tail(&self) -> Result<Self, &str> {
+        match self.trees.pop() {
+            Ok(((w, t), ts)) => {
+                let trees = match *t {
+                    SkewTree::Leaf(_) => ts,
+                    SkewTree::Node(_, ref t1, ref t2) =>
+                        ts.push((w / 2, t2.clone())).push((w / 2, t1.clone()))
+                };
+                Ok(PfSkewList { trees })
+            }
+            Err(_) => Err("tail for empty skew list")
+        }
+    }
+
+    pub fn push(&self, v: T) -> Self {
+        self.cons(v)
+    }
+
+    pub fn pop(&self) -> Result<(T, Self), &str> {
+        match (self.head(), self.tail()) {
+            (Ok(v), Ok(l)) => Ok((v, l)),
+            _ => Err("pop for empty skew list")
+        }
+    }
+
+    pub fn lookup(&self, i: usize) -> Result<T, &str> {
+        let mut i = i;
+        for &(w, ref t) in self.trees.iter() {
+            if i < w {
+                return Ok(t.lookup(w, i).clone());
+            }
+            i -= w;
+        }
+        Err("lookup out of range for skew list")
+    }
+
+    pub fn update(&self, i: usize, v: T) -> Result<Self, &str> {
+        match Self::update_trees(&self.trees, i, v) {
+            Some(trees) => Ok(PfSkewList { trees }),
+            None => Err("update out of range for skew list")
+        }
+    }
+}
+
+impl<T: Clone + Debug> Default for PfSkewList<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// 10.1.2 Binary random-access sequence
+//
+// `Seq a = Nil | Zero (Seq (a, a)) | One a (Seq (a, a))` is a nested
+// type: every level of the spine holds pairs of the elements of the
+// level above. Each level links to the next through a `Spine` trait
+// object, so the recursion runs through dynamic dispatch instead of
+// unfolding into an infinite family of types. The level marker `L`
+// bounds that family: after as many levels as a `usize` size can need,
+// the spine ends in `End`, which is always empty.
+#[derive(Debug)]
+struct Pair<E>(E, E);
+
+type Paired<E> = Ptr<Pair<E>>;
+type Link<'a, E> = Ptr<dyn Spine<'a, E> + 'a>;
+
+trait Spine<'a, E>: Debug + Shareable {
+    fn is_nil(&self) -> bool;
+    fn size_l(&self) -> usize;
+    fn size_s(&self) -> usize;
+    fn cons(&self, x: E) -> Link<'a, E>;
+    fn uncons(&self) -> Option<(E, Link<'a, E>)>;
+    fn lookup(&self, i: usize) -> Option<&E>;
+    fn fupdate(&self, f: &dyn Fn(&E) -> E, i: usize) -> Option<Link<'a, E>>;
+}
+
+trait Level: Debug + Shareable {
+    type Next: Level;
+
+    fn nil<'a, E>() -> Link<'a, E>
+        where E: 'a + Clone + Debug + Shareable;
+}
+
+#[derive(Debug)]
+enum Seq<'a, E, L> {
+    Nil(PhantomData<L>),
+    Zero(Link<'a, Paired<E>>),
+    One(E, Link<'a, Paired<E>>)
+}
+
+impl<'a, E, L> Seq<'a, E, L>
+    where E: 'a + Clone + Debug + Shareable, L: 'a + Level {
+    fn lookup_pairs<'s>(ps: &'s Link<'a, Paired<E>>, i: usize)
+                        -> Option<&'s E> {
+        let p = ps.lookup(i / 2)?;
+        Some(if i % 2 == 1 { &p.1 } else { &p.0 })
+    }
+}
+
+impl<'a, E, L> Spine<'a, E> for Seq<'a, E, L>
+    where E: 'a + Clone + Debug + Shareable, L: 'a + Level {
+    fn is_nil(&self) -> bool {
+        matches!(*self, Seq::Nil(_))
+    }
+
+    fn size_l(&self) -> usize {
+        match *self {
+            Seq::Nil(_) => 0,
+            Seq::Zero(ref ps) | Seq::One(_, ref ps) => 1 + ps.size_l()
+        }
+    }
+
+    fn size_s(&self) -> usize {
+        match *self {
+            Seq::Nil(_) => 0,
+            Seq::Zero(ref ps) => 2 * ps.size_s(),
+            Seq::One(_, ref ps) => 1 + 2 * ps.size_s()
+        }
+    }
+
+    fn cons(&self, x: E) -> Link<'a, E> {
+        Ptr::new(match *self {
+            Seq::Nil(_) => Self::One(x, L::Next::nil()),
+            Seq::Zero(ref ps) => Self::One(x, ps.clone()),
+            Seq::One(ref y, ref ps) =>
+                Self::Zero(ps.cons(Ptr::new(Pair(x, y.clone()))))
+        })
+    }
+
+    fn uncons(&self) -> Option<(E, Link<'a, E>)> {
+        match *self {
+            Seq::Nil(_) => None,
+            Seq::One(ref x, ref ps) => {
+                let s = if ps.is_nil() {
+                    Self::Nil(PhantomData)
+                } else {
+                    Self::Zero(ps.clone())
+                };
+                Some((x.clone(), Ptr::new(s)))
+            }
+            Seq::Zero(ref ps) => {
+                let (p, ps) = ps.uncons()?;
+                Some((p.0.clone(), Ptr::new(Self::One(p.1.clone(), ps))))
+            }
+        }
+    }
+
+    fn lookup(&self, i: usize) -> Option<&E> {
+        match *self {
+            Seq::Nil(_) => None,
+            Seq::One(ref x, ref ps) => {
+                if i == 0 {
+                    Some(x)
+                } else {
+                    Self::lookup_pairs(ps, i - 1)
+                }
+            }
+            Seq::Zero(ref ps) => Self::lookup_pairs(ps, i)
+        }
+    }
+
+    fn fupdate(&self, f: &dyn Fn(&E) -> E, i: usize) -> Option<Link<'a, E>> {
+        match *self {
+            Seq::Nil(_) => None,
+            Seq::One(ref x, ref ps) => {
+                if i == 0 {
+                    Some(Ptr::new(Self::One(f(x), ps.clone())))
+                } else {
+                    let s = Self::Zero(ps.clone()).fupdate(f, i - 1)?;
+                    Some(s.cons(x.clone()))
+                }
+            }
+            Seq::Zero(ref ps) => {
+                let g = |p: &Paired<E>| Ptr::new(if i % 2 == 1 {
+                    Pair(p.0.clone(), f(&p.1))
+                } else {
+                    Pair(f(&p.0), p.1.clone())
+                });
+                Some(Ptr::new(Self::Zero(ps.fupdate(&g, i / 2)?)))
+            }
+        }
+    }
+}
+
+// The spine below the last level. A sequence would need more elements
+// than a `usize` can count to reach it, so it never holds any.
+#[derive(Debug)]
+struct End;
+
+impl Level for End {
+    type Next = End;
+
+    fn nil<'a, E>() -> Link<'a, E>
+        where E: 'a + Clone + Debug + Shareable {
+        Ptr::new(End)
+    }
+}
+
+impl<'a, E: 'a + Clone + Debug + Shareable> Spine<'a, E> for End {
+    fn is_nil(&self) -> bool {
+        true
+    }
+
+    fn size_l(&self) -> usize {
+        0
+    }
+
+    fn size_s(&self) -> usize {
+        0
+    }
+
+    fn cons(&self, _: E) -> Link<'a, E> {
+        panic!("capacity overflow for binary sequence")
+    }
+
+    fn uncons(&self) -> Option<(E, Link<'a, E>)> {
+        None
+    }
+
+    fn lookup(&self, _: usize) -> Option<&E> {
+        None
+    }
+
+    fn fupdate(&self, _: &dyn Fn(&E) -> E, _: usize) -> Option<Link<'a, E>> {
+        None
+    }
+}
+
+macro_rules! seq_levels {
+    ($level:ident $next:ident $($rest:ident)*) => {
+        #[derive(Debug)]
+        struct $level;
+
+        impl Level for $level {
+            type Next = $next;
+
+            fn nil<'a, E>() -> Link<'a, E>
+                where E: 'a + Clone + Debug + Shareable {
+                Ptr::new(Seq::<E, $level>::Nil(PhantomData))
+            }
+        }
+
+        seq_levels!($next $($rest)*);
+    };
+    ($last:ident) => {};
+}
+
+seq_levels!(L0 L1 L2 L3 L4 L5 L6 L7 L8 L9 L10 L11 L12 L13 L14 L15
+            L16 L17 L18 L19 L20 L21 L22 L23 L24 L25 L26 L27 L28 L29 L30 L31
+            L32 L33 L34 L35 L36 L37 L38 L39 L40 L41 L42 L43 L44 L45 L46 L47
+            L48 L49 L50 L51 L52 L53 L54 L55 L56 L57 L58 L59 L60 L61 L62 L63
+            End);
+
+#[derive(Clone, Debug)]
+pub struct PfBinarySeq<'a, T: 'a + Clone + Debug> {
+    seq: Link<'a, T>
+}
+
+impl<'a, T: 'a + Clone + Debug + Shareable> PfBinarySeq<'a, T> {
+    pub fn new() -> Self {
+        PfBinarySeq { seq: L0::nil() }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.seq.is_nil()
+    }
+
+    // sizeS: the number of elements.
+    pub fn size(&self) -> usize {
+        self.seq.size_s()
+    }
+
+    // sizeL: the number of levels in the spine.
+    pub fn depth(&self) -> usize {
+        self.seq.size_l()
+    }
+
+    pub fn cons(&self, v: T) -> Self {
+        PfBinarySeq { seq: self.seq.cons(v) }
+    }
+
+    pub fn uncons(&self) -> Result<(T, Self), &str> {
+        match self.seq.uncons() {
+            Some((v, seq)) => Ok((v, PfBinarySeq { seq })),
+            None => Err("uncons for empty binary sequence")
+        }
+    }
+
+    pub fn lookup(&self, i: usize) -> Result<T, &str> {
+        match self.seq.lookup(i) {
+            Some(v) => Ok(v.clone()),
+            None => Err("lookup out of range for binary sequence")
+        }
+    }
+
+    pub fn update(&self, i: usize, v: T) -> Result<Self, &str> {
+        match self.seq.fupdate(&|_| v.clone(), i) {
+            Some(seq) => Ok(PfBinarySeq { seq }),
+            None => Err("update out of range for binary sequence")
+        }
+    }
+}
+
+impl<'a, T: 'a + Clone + Debug + Shareable> Default for PfBinarySeq<'a, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -76,6 +712,19 @@ mod tests {
         assert_eq!(42, v6);
     }
 
+    #[test]
+    fn test_head_tail() {
+        let l1 = PfList::<i32>::new();
+        let l2 = l1.push(42);
+        let l3 = l2.push(4);
+        assert_eq!(Ok(&4), l3.head());
+        assert_eq!(Ok(&42), l3.tail().unwrap().head());
+        assert!(l1.head().is_err());
+        assert!(l2.tail().unwrap().tail().is_err());
+        assert_eq!(vec![&4, &42], l3.iter().collect::<Vec<&i32>>());
+        assert!(l1.iter().next().is_none());
+    }
+
     #[test]
     fn test_rev() {
         let l = PfList::<i32>::new().push(1).push(2).push(3);
@@ -85,4 +734,160 @@ mod tests {
         let (v, _) = r.pop().unwrap();
         assert_eq!(1, v);
     }
+
+    #[test]
+    fn test_append() {
+        let l1 = PfList::<i32>::new().push(2).push(1);
+        let l2 = PfList::<i32>::new().push(4).push(3);
+        let l = l1.append(&l2);
+        let (v, l) = l.pop().unwrap();
+        assert_eq!(1, v);
+        let (v, l) = l.pop().unwrap();
+        assert_eq!(2, v);
+        let (v, _) = l.pop().unwrap();
+        assert_eq!(3, v);
+        assert!(PfList::new().append(&l1).pop().is_ok());
+        assert!(l1.append(&PfList::new()).pop().is_ok());
+    }
+
+    #[test]
+    fn test_random_access_list() {
+        let l1 = PfRandomAccessList::<i32>::new();
+        assert!(l1.is_empty());
+        assert!(l1.head().is_err());
+        assert!(l1.lookup(0).is_err());
+        let mut l = l1.clone();
+        for i in (0..100).rev() {
+            l = l.cons(i);
+        }
+        assert_eq!(100, l.len());
+        assert_eq!(0, l.head().unwrap());
+        for i in 0..100 {
+            assert_eq!(i as i32, l.lookup(i).unwrap());
+        }
+        assert!(l.lookup(100).is_err());
+        assert!(l.update(100, 0).is_err());
+
+        let l2 = l.update(42, -42).unwrap().update(99, -99).unwrap();
+        assert_eq!(-42, l2.lookup(42).unwrap());
+        assert_eq!(-99, l2.lookup(99).unwrap());
+        assert_eq!(42, l.lookup(42).unwrap());
+        assert_eq!(99, l.lookup(99).unwrap());
+
+        let mut t = l2.clone();
+        for i in 0..100 {
+            assert_eq!(100 - i, t.len());
+            assert_eq!(l2.lookup(i).unwrap(), t.head().unwrap());
+            t = t.tail().unwrap();
+        }
+        assert!(t.is_empty());
+        assert!(t.tail().is_err());
+    }
+
+    #[test]
+    fn test_skew_list() {
+        let l1 = PfSkewList::<i32>::new();
+        assert!(l1.is_empty());
+        let l2 = l1.push(42);
+        assert!(!l2.is_empty());
+        let l3 = l2.push(4);
+        let (v4, l4) = l3.pop().unwrap();
+        assert_eq!(4, v4);
+        let (v5, l5) = l4.pop().unwrap();
+        assert_eq!(42, v5);
+        assert!(l5.is_empty());
+        assert!(l5.pop().is_err());
+
+        let mut l = PfSkewList::new();
+        for i in (0..100).rev() {
+            l = l.push(i);
+        }
+        assert_eq!(100, l.len());
+        for i in 0..100 {
+            assert_eq!(i as i32, l.lookup(i).unwrap());
+        }
+        assert!(l.lookup(100).is_err());
+        let u = l.update(0, -1).unwrap().update(57, -57).unwrap();
+        assert_eq!(-1, u.lookup(0).unwrap());
+        assert_eq!(-57, u.lookup(57).unwrap());
+        assert_eq!(57, l.lookup(57).unwrap());
+
+        let mut t = u.clone();
+        for i in 0..100 {
+            let (v, nt) = t.pop().unwrap();
+            assert_eq!(u.lookup(i).unwrap(), v);
+            assert_eq!(99 - i, nt.len());
+            t = nt;
+        }
+        assert!(t.is_empty());
+    }
+
+    #[test]
+    fn test_skew_list_cons() {
+        let l1 = PfSkewList::<i32>::new();
+        assert!(l1.head().is_err());
+        assert!(l1.tail().is_err());
+        let mut l = l1.clone();
+        for i in (0..100).rev() {
+            l = l.cons(i);
+        }
+        assert_eq!(0, l.head().unwrap());
+
+        let mut t = l.clone();
+        for i in 0..100 {
+            assert_eq!(100 - i, t.len());
+            assert_eq!(l.lookup(i).unwrap(), t.head().unwrap());
+            t = t.tail().unwrap();
+        }
+        assert!(t.is_empty());
+        assert!(t.tail().is_err());
+    }
+
+    #[test]
+    fn test_binary_seq() {
+        let s = PfBinarySeq::<i32>::new();
+        assert_eq!(0, s.depth());
+        assert_eq!(0, s.size());
+
+        let s = s.cons(1);
+        assert_eq!(1, s.depth());
+        assert_eq!(1, s.size());
+
+        let s = PfBinarySeq::new().cons(3).cons(2).cons(1);
+        assert_eq!(2, s.depth());
+        assert_eq!(3, s.size());
+
+        let mut s = PfBinarySeq::new();
+        for i in (1..8).rev() {
+            s = s.cons(i);
+        }
+        assert_eq!(3, s.depth());
+        assert_eq!(7, s.size());
+
+        let mut s = PfBinarySeq::new();
+        for i in (0..100).rev() {
+            s = s.cons(i);
+        }
+        assert_eq!(100, s.size());
+        for i in 0..100 {
+            assert_eq!(i as i32, s.lookup(i).unwrap());
+        }
+        assert!(s.lookup(100).is_err());
+        assert!(s.update(100, 0).is_err());
+        let u = s.update(0, -1).unwrap().update(57, -57).unwrap();
+        assert_eq!(-1, u.lookup(0).unwrap());
+        assert_eq!(-57, u.lookup(57).unwrap());
+        assert_eq!(57, s.lookup(57).unwrap());
+        assert_eq!(100, u.size());
+
+        let mut t = u.clone();
+        for i in 0..100 {
+            let (v, nt) = t.uncons().unwrap();
+            assert_eq!(u.lookup(i).unwrap(), v);
+            assert_eq!(99 - i, nt.size());
+            t = nt;
+        }
+        assert!(t.is_empty());
+        assert!(t.uncons().is_err());
+    }
 }
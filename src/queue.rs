@@ -1,6 +1,7 @@
 use std::fmt::Debug;
 use std::fmt::Display;
 
+use lazy::Shareable;
 use lazy::Thunk;
 use list::PfList;
 use stream::Stream;
@@ -9,8 +10,22 @@ pub trait PfQueue<T: Clone + Display + Debug>: Clone + Sized {
     fn new() -> Self;
     fn is_empty(&self) -> bool;
     fn snoc(&self, v: T) -> Self;
-    fn head(&self) -> Result<T, &str>;
+    fn head_ref(&self) -> Result<&T, &str>;
     fn tail(&self) -> Result<Self, &str>;
+
+    fn head<'s>(&'s self) -> Result<T, &'s str> where T: 's {
+        self.head_ref().cloned()
+    }
+}
+
+pub trait PfDeque<T: Clone + Display + Debug>: PfQueue<T> {
+    fn cons(&self, v: T) -> Self;
+    fn last_ref(&self) -> Result<&T, &str>;
+    fn init(&self) -> Result<Self, &str>;
+
+    fn last<'s>(&'s self) -> Result<T, &'s str> where T: 's {
+        self.last_ref().cloned()
+    }
 }
 
 // 5.2 Batched queue
@@ -46,16 +61,16 @@ impl<T: Clone + Display + Debug> PfQueue<T> for PfBatchedQueue<T> {
         Self::checkf(&self.f, &self.r.push(v))
     }
 
-    fn head(&self) -> Result<T, &str> {
-        match self.f.pop() {
-            Ok((v, _)) => Ok(v),
+    fn head_ref(&self) -> Result<&T, &str> {
+        match self.f.head() {
+            Ok(v) => Ok(v),
             Err(_) => Err("head for empty batched queue")
         }
     }
 
     fn tail(&self) -> Result<Self, &str> {
-        match self.f.pop() {
-            Ok((_, f)) => Ok(Self::checkf(&f, &self.r)),
+        match self.f.tail() {
+            Ok(f) => Ok(Self::checkf(&f, &self.r)),
             Err(_) => Err("tail for empty batched queue")
         }
     }
@@ -70,13 +85,13 @@ pub struct PfBankerQueue<'a, T: 'a + Clone + Display + Debug> {
     r: Stream<'a, T>,
 }
 
-impl<'a, T: 'a + Clone + Display + Debug> PfBankerQueue<'a, T> {
+impl<'a, T: 'a + Clone + Display + Debug + Shareable> PfBankerQueue<'a, T> {
     fn check(fl: i32, f: Stream<'a, T>, rl: i32, r: Stream<'a, T>) -> Self {
         if rl < fl {
             Self {
-                fl: fl,
+                fl,
                 f: f.clone(),
-                rl: rl,
+                rl,
                 r: r.clone(),
             }
         } else {
@@ -90,7 +105,8 @@ impl<'a, T: 'a + Clone + Display + Debug> PfBankerQueue<'a, T> {
     }
 }
 
-impl<'a, T: 'a + Clone + Display + Debug> PfQueue<T> for PfBankerQueue<'a, T> {
+impl<'a, T: 'a + Clone + Display + Debug + Shareable> PfQueue<T>
+    for PfBankerQueue<'a, T> {
     fn new() -> Self {
         Self {
             fl: 0,
@@ -108,8 +124,8 @@ impl<'a, T: 'a + Clone + Display + Debug> PfQueue<T> for PfBankerQueue<'a, T> {
         Self::check(self.fl, self.f.clone(), self.rl + 1, self.r.push(v))
     }
 
-    fn head(&self) -> Result<T, &str> {
-        match self.f.head() {
+    fn head_ref(&self) -> Result<&T, &str> {
+        match self.f.head_ref() {
             Ok(v) => Ok(v),
             Err(_) => Err("head for empty banker queue")
         }
@@ -123,6 +139,79 @@ impl<'a, T: 'a + Clone + Display + Debug> PfQueue<T> for PfBankerQueue<'a, T> {
     }
 }
 
+// 6.4.2 Physicist queue
+#[derive(Clone, Debug)]
+pub struct PfPhysicistQueue<'a, T: 'a + Clone + Display + Debug> {
+    w: PfList<T>,
+    fl: i32,
+    f: Thunk<'a, PfList<T>>,
+    rl: i32,
+    r: PfList<T>,
+}
+
+impl<'a, T: 'a + Clone + Display + Debug + Shareable> PfPhysicistQueue<'a, T> {
+    fn checkw(w: PfList<T>, fl: i32, f: Thunk<'a, PfList<T>>,
+              rl: i32, r: PfList<T>) -> Self {
+        if w.is_empty() {
+            Self { w: f.force().clone(), fl, f, rl, r }
+        } else {
+            Self { w, fl, f, rl, r }
+        }
+    }
+
+    fn check(w: PfList<T>, fl: i32, f: Thunk<'a, PfList<T>>,
+             rl: i32, r: PfList<T>) -> Self {
+        if rl <= fl {
+            Self::checkw(w, fl, f, rl, r)
+        } else {
+            let fd = f.force().clone();
+            let nf = fd.clone();
+            Self::checkw(fd, fl + rl, lazy!(nf.append(&r.rev())),
+                         0, PfList::new())
+        }
+    }
+}
+
+impl<'a, T: 'a + Clone + Display + Debug + Shareable> PfQueue<T>
+    for PfPhysicistQueue<'a, T> {
+    fn new() -> Self {
+        Self {
+            w: PfList::new(),
+            fl: 0,
+            f: lazy!(PfList::new()),
+            rl: 0,
+            r: PfList::new()
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.fl == 0
+    }
+
+    fn snoc(&self, v: T) -> Self {
+        Self::check(self.w.clone(), self.fl, self.f.clone(),
+                    self.rl + 1, self.r.push(v))
+    }
+
+    fn head_ref(&self) -> Result<&T, &str> {
+        match self.w.head() {
+            Ok(v) => Ok(v),
+            Err(_) => Err("head for empty physicist queue")
+        }
+    }
+
+    fn tail(&self) -> Result<Self, &str> {
+        match self.w.tail() {
+            Ok(w) => {
+                let f = self.f.clone();
+                Ok(Self::check(w, self.fl - 1, lazy!(f.force().tail().unwrap()),
+                               self.rl, self.r.clone()))
+            }
+            Err(_) => Err("tail for empty physicist queue")
+        }
+    }
+}
+
 // 7.2 Real time queue
 #[derive(Clone, Debug)]
 pub struct PfRealTimeQueue<'a, T: 'a + Clone + Display + Debug> {
@@ -131,16 +220,14 @@ pub struct PfRealTimeQueue<'a, T: 'a + Clone + Display + Debug> {
     s: Stream<'a, T>
 }
 
-impl<'a, T: 'a + Clone + Display + Debug> PfRealTimeQueue<'a, T> {
+impl<'a, T: 'a + Clone + Display + Debug + Shareable> PfRealTimeQueue<'a, T> {
     fn rotate(f: Stream<'a, T>, r: PfList<T>, a: Stream<'a, T>)
               -> Stream<'a, T> {
         let (y, ys) = r.pop().unwrap();
         let na = a.push(y);
         match f.pop() {
             Ok((x, xs)) => {
-                Stream::new(lazy!(
-                    Self::rotate(xs.clone(), ys.clone(), na.clone()).eval()
-                )).push(x.clone())
+                Stream::new(lazy!(Self::rotate(xs, ys, na).eval())).push(x)
             }
             Err(_) => {
                 na
@@ -150,28 +237,20 @@ impl<'a, T: 'a + Clone + Display + Debug> PfRealTimeQueue<'a, T> {
 
     fn exec(f: Stream<'a, T>, r: PfList<T>, s: Stream<'a, T>) -> Self {
         match s.tail() {
-            Ok(t) => {
-                return Self {
-                    f: f.clone(),
-                    r: r.clone(),
-                    s: t.clone()
-                }
-            }
+            Ok(t) => Self { f, r, s: t },
             Err(_) => {
-                let fd = Self::rotate(f.clone(),
-                                      r.clone(),
-                                      Stream::empty());
-                return Self {
+                let fd = Self::rotate(f, r, Stream::empty());
+                Self {
                     f: fd.clone(),
                     r: PfList::new(),
-                    s: fd.clone()
+                    s: fd
                 }
             }
         }
     }
 }
 
-impl<'a, T: 'a + Clone + Display + Debug> PfQueue<T>
+impl<'a, T: 'a + Clone + Display + Debug + Shareable> PfQueue<T>
     for PfRealTimeQueue<'a, T> {
     fn new() -> Self {
         Self {
@@ -182,7 +261,7 @@ impl<'a, T: 'a + Clone + Display + Debug> PfQueue<T>
     }
 
     fn is_empty(&self) -> bool {
-        self.f.head().is_err()
+        self.f.head_ref().is_err()
     }
 
     fn snoc(&self, v: T) -> Self {
@@ -190,8 +269,8 @@ impl<'a, T: 'a + Clone + Display + Debug> PfQueue<T>
         Self::exec(self.f.clone(), r, self.s.clone())
     }
 
-    fn head(&self) -> Result<T, &str> {
-        match self.f.head() {
+    fn head_ref(&self) -> Result<&T, &str> {
+        match self.f.head_ref() {
             Ok(v) => Ok(v),
             Err(_) => Err("head for empty real time queue")
         }
@@ -205,9 +284,417 @@ impl<'a, T: 'a + Clone + Display + Debug> PfQueue<T>
     }
 }
 
+// 8.2.1 Hood-Melville real time queue
+#[derive(Clone, Debug)]
+enum RotationState<T: Clone + Display + Debug> {
+    Idle,
+    Reversing {
+        ok: i32,
+        f: PfList<T>,
+        fd: PfList<T>,
+        r: PfList<T>,
+        rd: PfList<T>,
+    },
+    Appending {
+        ok: i32,
+        fd: PfList<T>,
+        rd: PfList<T>,
+    },
+    Done(PfList<T>)
+}
+
+impl<T: Clone + Display + Debug> RotationState<T> {
+    fn exec(&self) -> Self {
+        use self::RotationState::*;
+        match *self {
+            Reversing { ok, ref f, ref fd, ref r, ref rd } => {
+                match (f.pop(), r.pop()) {
+                    (Ok((x, f)), Ok((y, r))) => Reversing {
+                        ok: ok + 1,
+                        f,
+                        fd: fd.push(x),
+                        r,
+                        rd: rd.push(y)
+                    },
+                    (Err(_), Ok((y, _))) => Appending {
+                        ok,
+                        fd: fd.clone(),
+                        rd: rd.push(y)
+                    },
+                    _ => unreachable!("rear of rotation ran out early")
+                }
+            }
+            Appending { ok: 0, ref rd, .. } => Done(rd.clone()),
+            Appending { ok, ref fd, ref rd } => {
+                let (x, fd) = fd.pop().unwrap();
+                Appending { ok: ok - 1, fd, rd: rd.push(x) }
+            }
+            _ => self.clone()
+        }
+    }
+
+    fn invalidate(&self) -> Self {
+        use self::RotationState::*;
+        match *self {
+            Reversing { ok, ref f, ref fd, ref r, ref rd } => Reversing {
+                ok: ok - 1,
+                f: f.clone(),
+                fd: fd.clone(),
+                r: r.clone(),
+                rd: rd.clone()
+            },
+            Appending { ok: 0, ref rd, .. } => Done(rd.tail().unwrap()),
+            Appending { ok, ref fd, ref rd } => Appending {
+                ok: ok - 1,
+                fd: fd.clone(),
+                rd: rd.clone()
+            },
+            _ => self.clone()
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PfHoodMelvilleQueue<T: Clone + Display + Debug> {
+    fl: i32,
+    f: PfList<T>,
+    state: RotationState<T>,
+    rl: i32,
+    r: PfList<T>,
+}
+
+impl<T: Clone + Display + Debug> PfHoodMelvilleQueue<T> {
+    fn exec2(fl: i32, f: PfList<T>, state: RotationState<T>,
+             rl: i32, r: PfList<T>) -> Self {
+        match state.exec().exec() {
+            RotationState::Done(f) => {
+                Self { fl, f, state: RotationState::Idle, rl, r }
+            }
+            state => Self { fl, f, state, rl, r }
+        }
+    }
+
+    fn check(fl: i32, f: PfList<T>, state: RotationState<T>,
+             rl: i32, r: PfList<T>) -> Self {
+        if rl <= fl {
+            Self::exec2(fl, f, state, rl, r)
+        } else {
+            let state = RotationState::Reversing {
+                ok: 0,
+                f: f.clone(),
+                fd: PfList::new(),
+                r,
+                rd: PfList::new()
+            };
+            Self::exec2(fl + rl, f, state, 0, PfList::new())
+        }
+    }
+}
+
+impl<T: Clone + Display + Debug> PfQueue<T> for PfHoodMelvilleQueue<T> {
+    fn new() -> Self {
+        Self {
+            fl: 0,
+            f: PfList::new(),
+            state: RotationState::Idle,
+            rl: 0,
+            r: PfList::new()
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.fl == 0
+    }
+
+    fn snoc(&self, v: T) -> Self {
+        Self::check(self.fl, self.f.clone(), self.state.clone(),
+                    self.rl + 1, self.r.push(v))
+    }
+
+    fn head_ref(&self) -> Result<&T, &str> {
+        match self.f.head() {
+            Ok(v) => Ok(v),
+            Err(_) => Err("head for empty hood melville queue")
+        }
+    }
+
+    fn tail(&self) -> Result<Self, &str> {
+        match self.f.tail() {
+            Ok(f) => Ok(Self::check(self.fl - 1, f,
+                                    self.state.invalidate(),
+                                    self.rl, self.r.clone())),
+            Err(_) => Err("tail for empty hood melville queue")
+        }
+    }
+}
+
+// 8.4.2 Banker deque
+#[derive(Clone, Debug)]
+pub struct PfBankerDeque<'a, T: 'a + Clone + Display + Debug> {
+    c: i32,
+    fl: i32,
+    f: Stream<'a, T>,
+    rl: i32,
+    r: Stream<'a, T>,
+}
+
+impl<'a, T: 'a + Clone + Display + Debug + Shareable> PfBankerDeque<'a, T> {
+    pub fn with_balance(c: i32) -> Self {
+        assert!(c > 1, "balance factor of banker deque must be > 1");
+        Self {
+            c,
+            fl: 0,
+            f: Stream::empty(),
+            rl: 0,
+            r: Stream::empty()
+        }
+    }
+
+    fn check(&self, fl: i32, f: Stream<'a, T>, rl: i32, r: Stream<'a, T>)
+             -> Self {
+        let c = self.c;
+        if fl > c * rl + 1 {
+            let i = (fl + rl) / 2;
+            let j = fl + rl - i;
+            Self {
+                c,
+                fl: i,
+                f: f.take_n(i as usize),
+                rl: j,
+                r: r.concat(f.drop_n(i as usize).reverse()),
+            }
+        } else if rl > c * fl + 1 {
+            let j = (fl + rl) / 2;
+            let i = fl + rl - j;
+            Self {
+                c,
+                fl: i,
+                f: f.concat(r.drop_n(j as usize).reverse()),
+                rl: j,
+                r: r.take_n(j as usize),
+            }
+        } else {
+            Self { c, fl, f, rl, r }
+        }
+    }
+}
+
+impl<'a, T: 'a + Clone + Display + Debug + Shareable> PfQueue<T>
+    for PfBankerDeque<'a, T> {
+    fn new() -> Self {
+        Self::with_balance(3)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.fl + self.rl == 0
+    }
+
+    fn snoc(&self, v: T) -> Self {
+        self.check(self.fl, self.f.clone(), self.rl + 1, self.r.push(v))
+    }
+
+    fn head_ref(&self) -> Result<&T, &str> {
+        match self.f.head_ref() {
+            Ok(v) => Ok(v),
+            Err(_) => match self.r.head_ref() {
+                Ok(v) => Ok(v),
+                Err(_) => Err("head for empty banker deque")
+            }
+        }
+    }
+
+    fn tail(&self) -> Result<Self, &str> {
+        match self.f.tail() {
+            Ok(f) => Ok(self.check(self.fl - 1, f, self.rl, self.r.clone())),
+            Err(_) => match self.r.tail() {
+                Ok(_) => Ok(Self::with_balance(self.c)),
+                Err(_) => Err("tail for empty banker deque")
+            }
+        }
+    }
+}
+
+impl<'a, T: 'a + Clone + Display + Debug + Shareable> PfDeque<T>
+    for PfBankerDeque<'a, T> {
+    fn cons(&self, v: T) -> Self {
+        self.check(self.fl + 1, self.f.push(v), self.rl, self.r.clone())
+    }
+
+    fn last_ref(&self) -> Result<&T, &str> {
+        match self.r.head_ref() {
+            Ok(v) => Ok(v),
+            Err(_) => match self.f.head_ref() {
+                Ok(v) => Ok(v),
+                Err(_) => Err("last for empty banker deque")
+            }
+        }
+    }
+
+    fn init(&self) -> Result<Self, &str> {
+        match self.r.tail() {
+            Ok(r) => Ok(self.check(self.fl, self.f.clone(), self.rl - 1, r)),
+            Err(_) => match self.f.tail() {
+                Ok(_) => Ok(Self::with_balance(self.c)),
+                Err(_) => Err("init for empty banker deque")
+            }
+        }
+    }
+}
+
+// 8.4.3 Real time deque
+const REAL_TIME_DEQUE_C: i32 = 3;
+
+#[derive(Clone, Debug)]
+pub struct PfRealTimeDeque<'a, T: 'a + Clone + Display + Debug> {
+    fl: i32,
+    f: Stream<'a, T>,
+    sf: Stream<'a, T>,
+    rl: i32,
+    r: Stream<'a, T>,
+    sr: Stream<'a, T>,
+}
+
+impl<'a, T: 'a + Clone + Display + Debug + Shareable> PfRealTimeDeque<'a, T> {
+    fn exec1(s: &Stream<'a, T>) -> Stream<'a, T> {
+        match s.tail() {
+            Ok(t) => t,
+            Err(_) => s.clone()
+        }
+    }
+
+    fn exec2(s: &Stream<'a, T>) -> Stream<'a, T> {
+        Self::exec1(&Self::exec1(s))
+    }
+
+    fn rotate_rev(r: Stream<'a, T>, f: Stream<'a, T>, a: Stream<'a, T>)
+                  -> Stream<'a, T> {
+        const C: usize = REAL_TIME_DEQUE_C as usize;
+        Stream::new(lazy!(
+            match r.pop() {
+                Ok((x, rs)) => {
+                    let a = f.take_n(C).reverse().concat(a);
+                    Self::rotate_rev(rs, f.drop_n(C), a).push(x).eval()
+                }
+                Err(_) => f.reverse().concat(a).eval()
+            }
+        ))
+    }
+
+    fn rotate_drop(r: Stream<'a, T>, i: i32, f: Stream<'a, T>)
+                   -> Stream<'a, T> {
+        const C: usize = REAL_TIME_DEQUE_C as usize;
+        if i < REAL_TIME_DEQUE_C {
+            return Self::rotate_rev(r, f.drop_n(i as usize), Stream::empty());
+        }
+        Stream::new(lazy!({
+            let (x, rs) = r.pop().unwrap();
+            Self::rotate_drop(rs, i - REAL_TIME_DEQUE_C, f.drop_n(C))
+                .push(x).eval()
+        }))
+    }
+
+    fn check(fl: i32, f: Stream<'a, T>, sf: Stream<'a, T>,
+             rl: i32, r: Stream<'a, T>, sr: Stream<'a, T>) -> Self {
+        const C: i32 = REAL_TIME_DEQUE_C;
+        if fl > C * rl + 1 {
+            let i = (fl + rl) / 2;
+            let j = fl + rl - i;
+            let nf = f.take_n(i as usize);
+            let nr = Self::rotate_drop(r, i, f);
+            Self { fl: i, f: nf.clone(), sf: nf, rl: j, r: nr.clone(), sr: nr }
+        } else if rl > C * fl + 1 {
+            let j = (fl + rl) / 2;
+            let i = fl + rl - j;
+            let nr = r.take_n(j as usize);
+            let nf = Self::rotate_drop(f, j, r);
+            Self { fl: i, f: nf.clone(), sf: nf, rl: j, r: nr.clone(), sr: nr }
+        } else {
+            Self { fl, f, sf, rl, r, sr }
+        }
+    }
+}
+
+impl<'a, T: 'a + Clone + Display + Debug + Shareable> PfQueue<T>
+    for PfRealTimeDeque<'a, T> {
+    fn new() -> Self {
+        Self {
+            fl: 0,
+            f: Stream::empty(),
+            sf: Stream::empty(),
+            rl: 0,
+            r: Stream::empty(),
+            sr: Stream::empty()
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.fl + self.rl == 0
+    }
+
+    fn snoc(&self, v: T) -> Self {
+        Self::check(self.fl, self.f.clone(), Self::exec1(&self.sf),
+                    self.rl + 1, self.r.push(v), Self::exec1(&self.sr))
+    }
+
+    fn head_ref(&self) -> Result<&T, &str> {
+        match self.f.head_ref() {
+            Ok(v) => Ok(v),
+            Err(_) => match self.r.head_ref() {
+                Ok(v) => Ok(v),
+                Err(_) => Err("head for empty real time deque")
+            }
+        }
+    }
+
+    fn tail(&self) -> Result<Self, &str> {
+        match self.f.tail() {
+            Ok(f) => Ok(Self::check(self.fl - 1, f, Self::exec2(&self.sf),
+                                    self.rl, self.r.clone(),
+                                    Self::exec2(&self.sr))),
+            Err(_) => match self.r.tail() {
+                Ok(_) => Ok(Self::new()),
+                Err(_) => Err("tail for empty real time deque")
+            }
+        }
+    }
+}
+
+impl<'a, T: 'a + Clone + Display + Debug + Shareable> PfDeque<T>
+    for PfRealTimeDeque<'a, T> {
+    fn cons(&self, v: T) -> Self {
+        Self::check(self.fl + 1, self.f.push(v), Self::exec1(&self.sf),
+                    self.rl, self.r.clone(), Self::exec1(&self.sr))
+    }
+
+    fn last_ref(&self) -> Result<&T, &str> {
+        match self.r.head_ref() {
+            Ok(v) => Ok(v),
+            Err(_) => match self.f.head_ref() {
+                Ok(v) => Ok(v),
+                Err(_) => Err("last for empty real time deque")
+            }
+        }
+    }
+
+    fn init(&self) -> Result<Self, &str> {
+        match self.r.tail() {
+            Ok(r) => Ok(Self::check(self.fl, self.f.clone(),
+                                    Self::exec2(&self.sf), self.rl - 1, r,
+                                    Self::exec2(&self.sr))),
+            Err(_) => match self.f.tail() {
+                Ok(_) => Ok(Self::new()),
+                Err(_) => Err("init for empty real time deque")
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::VecDeque;
+    use std::ptr;
+    use Ptr;
 
     fn test_pf_queue<Queue: PfQueue<i32>>() {
         let q1 = Queue::new();
@@ -221,6 +708,71 @@ mod tests {
         assert_eq!(2, q4.tail().unwrap().tail().unwrap().head().unwrap());
     }
 
+    fn test_pf_queue_ref<Queue: PfQueue<String>>() {
+        let q = Queue::new();
+        assert!(q.head_ref().is_err());
+        let q = q.snoc("a".to_string()).snoc("b".to_string());
+        assert_eq!(Ok(&"a".to_string()), q.head_ref());
+        // Repeated reads borrow the stored element rather than a copy.
+        assert!(ptr::eq(q.head_ref().unwrap(), q.head_ref().unwrap()));
+        assert_eq!(Ok(&"b".to_string()), q.tail().unwrap().head_ref());
+    }
+
+    fn test_pf_deque_ref<Deque: PfDeque<String>>() {
+        let d = Deque::new();
+        assert!(d.last_ref().is_err());
+        let d = d.cons("a".to_string()).snoc("b".to_string());
+        assert_eq!(Ok(&"b".to_string()), d.last_ref());
+        assert!(ptr::eq(d.last_ref().unwrap(), d.last_ref().unwrap()));
+        assert_eq!(Ok(&"a".to_string()), d.init().unwrap().last_ref());
+    }
+
+    fn test_pf_deque<Deque: PfDeque<i32>>() {
+        let d1 = Deque::new();
+        assert!(d1.last().is_err());
+        assert!(d1.init().is_err());
+        let d2 = d1.cons(1);
+        assert_eq!(1, d2.head().unwrap());
+        assert_eq!(1, d2.last().unwrap());
+        assert!(d2.init().unwrap().is_empty());
+        let d3 = d2.cons(0).snoc(2).snoc(3);
+        assert_eq!(0, d3.head().unwrap());
+        assert_eq!(3, d3.last().unwrap());
+        assert_eq!(2, d3.init().unwrap().last().unwrap());
+        assert_eq!(1, d3.tail().unwrap().head().unwrap());
+        assert_eq!(1, d2.last().unwrap());
+
+        let mut d = Deque::new();
+        let mut model = VecDeque::new();
+        let mut x: i32 = 1;
+        for i in 0..1000 {
+            x = (x * 1103 + 12345) % 1000;
+            match x % 4 {
+                0 => {
+                    d = d.cons(i);
+                    model.push_front(i);
+                }
+                1 => {
+                    d = d.snoc(i);
+                    model.push_back(i);
+                }
+                2 => {
+                    assert_eq!(model.pop_front(), d.head().ok());
+                    if let Ok(nd) = d.tail() {
+                        d = nd;
+                    }
+                }
+                _ => {
+                    assert_eq!(model.pop_back(), d.last().ok());
+                    if let Ok(nd) = d.init() {
+                        d = nd;
+                    }
+                }
+            }
+            assert_eq!(model.is_empty(), d.is_empty());
+        }
+    }
+
     #[test]
     fn test_pf_batched_queue() {
         test_pf_queue::<PfBatchedQueue<i32>>();
@@ -231,8 +783,170 @@ mod tests {
         test_pf_queue::<PfBankerQueue<i32>>();
     }
 
+    #[test]
+    fn test_pf_physicist_queue() {
+        test_pf_queue::<PfPhysicistQueue<i32>>();
+    }
+
+    #[test]
+    fn test_pf_physicist_queue_model() {
+        let mut q = PfPhysicistQueue::new();
+        let mut model = VecDeque::new();
+        let mut rotations = 0;
+        let mut x: i32 = 1;
+        for i in 0..2000 {
+            x = (x * 1103 + 12345) % 1000;
+            let rl = q.rl;
+            // Two snocs for every tail on average, so r keeps outgrowing f.
+            if x % 3 < 2 {
+                q = q.snoc(i);
+                model.push_back(i);
+            } else {
+                assert_eq!(model.pop_front(), q.head().ok());
+                if let Ok(nq) = q.tail() {
+                    q = nq;
+                }
+            }
+            if q.rl < rl {
+                rotations += 1;
+            }
+            assert!(q.rl <= q.fl);
+            assert_eq!(model.len() as i32, q.fl + q.rl);
+            assert_eq!(model.is_empty(), q.is_empty());
+        }
+        assert!(rotations > 5);
+        while let Some(v) = model.pop_front() {
+            assert_eq!(v, q.head().unwrap());
+            q = q.tail().unwrap();
+        }
+        assert!(q.is_empty());
+    }
+
     #[test]
     fn test_pf_real_time_queue() {
         test_pf_queue::<PfRealTimeQueue<i32>>();
     }
+
+    #[cfg(feature = "sync")]
+    #[test]
+    fn test_pf_queue_sync() {
+        use std::thread;
+
+        let mut q = PfRealTimeQueue::new();
+        for i in 0..100 {
+            q = q.snoc(i);
+        }
+        let handles: Vec<_> = (0..4).map(|_| {
+            let q = q.clone();
+            thread::spawn(move || {
+                let mut q = q;
+                let mut v = Vec::new();
+                while let Ok(x) = q.head() {
+                    v.push(x);
+                    q = q.tail().unwrap();
+                }
+                v
+            })
+        }).collect();
+        for h in handles {
+            assert_eq!((0..100).collect::<Vec<i32>>(), h.join().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_pf_physicist_queue_releases_elements() {
+        let xs: Vec<Ptr<i32>> = (0..100).map(Ptr::new).collect();
+        let mut q = PfPhysicistQueue::new();
+        for x in &xs {
+            q = q.snoc(x.clone());
+        }
+        for _ in 0..50 {
+            q = q.tail().unwrap();
+        }
+        // Once the front is forced, the suspensions it went through must
+        // not keep dequeued elements alive.
+        q.f.eval();
+        for x in &xs[..50] {
+            assert_eq!(1, Ptr::strong_count(x));
+        }
+        assert_eq!(50, xs.iter().filter(|x| Ptr::strong_count(x) > 1).count());
+    }
+
+    #[test]
+    fn test_pf_real_time_queue_releases_elements() {
+        let xs: Vec<Ptr<i32>> = (0..100).map(Ptr::new).collect();
+        let mut q = PfRealTimeQueue::new();
+        for x in &xs {
+            q = q.snoc(x.clone());
+        }
+        for (i, x) in xs.iter().enumerate() {
+            assert_eq!(x, &q.head().unwrap());
+            q = q.tail().unwrap();
+            // Rotations suspended behind the front must not keep popped
+            // elements alive.
+            assert_eq!(1, Ptr::strong_count(x));
+            assert_eq!(99 - i, xs.iter()
+                       .filter(|x| Ptr::strong_count(x) > 1).count());
+        }
+        assert!(q.is_empty());
+    }
+
+    #[test]
+    fn test_pf_hood_melville_queue() {
+        test_pf_queue::<PfHoodMelvilleQueue<i32>>();
+
+        let mut q = PfHoodMelvilleQueue::new();
+        let mut model = VecDeque::new();
+        for i in 0..1000 {
+            if i % 5 < 3 {
+                q = q.snoc(i);
+                model.push_back(i);
+            } else {
+                assert_eq!(model.pop_front(), q.head().ok());
+                if let Ok(nq) = q.tail() {
+                    q = nq;
+                }
+            }
+        }
+        while let Some(v) = model.pop_front() {
+            assert_eq!(v, q.head().unwrap());
+            q = q.tail().unwrap();
+        }
+        assert!(q.is_empty());
+    }
+
+    #[test]
+    fn test_pf_banker_deque() {
+        test_pf_queue::<PfBankerDeque<i32>>();
+        test_pf_deque::<PfBankerDeque<i32>>();
+
+        let mut d = PfBankerDeque::with_balance(2);
+        for i in 0..100 {
+            d = d.cons(i);
+        }
+        for i in 0..100 {
+            assert_eq!(i, d.last().unwrap());
+            d = d.init().unwrap();
+        }
+        assert!(d.is_empty());
+    }
+
+    #[test]
+    fn test_pf_real_time_deque() {
+        test_pf_queue::<PfRealTimeDeque<i32>>();
+        test_pf_deque::<PfRealTimeDeque<i32>>();
+    }
+
+    #[test]
+    fn test_head_ref() {
+        test_pf_queue_ref::<PfBatchedQueue<String>>();
+        test_pf_queue_ref::<PfBankerQueue<String>>();
+        test_pf_queue_ref::<PfPhysicistQueue<String>>();
+        test_pf_queue_ref::<PfRealTimeQueue<String>>();
+        test_pf_queue_ref::<PfHoodMelvilleQueue<String>>();
+        test_pf_queue_ref::<PfBankerDeque<String>>();
+        test_pf_queue_ref::<PfRealTimeDeque<String>>();
+        test_pf_deque_ref::<PfBankerDeque<String>>();
+        test_pf_deque_ref::<PfRealTimeDeque<String>>();
+    }
 }
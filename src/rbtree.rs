@@ -0,0 +1,523 @@
+// 3.3 Red-black tree
+
+use std::cmp::Ordering;
+use std::fmt::Debug;
+use std::ops::Bound;
+use std::ops::RangeBounds;
+
+use Ptr;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Color {
+    Red,
+    Black
+}
+
+#[derive(Debug)]
+struct RbNode<K: Ord + Clone + Debug, V: Clone + Debug> {
+    color: Color,
+    left: RbTree<K, V>,
+    key: K,
+    value: V,
+    right: RbTree<K, V>,
+}
+
+type RbTree<K, V> = Option<Ptr<RbNode<K, V>>>;
+
+fn node<K, V>(color: Color, left: RbTree<K, V>, key: K, value: V,
+              right: RbTree<K, V>) -> RbTree<K, V>
+    where K: Ord + Clone + Debug, V: Clone + Debug {
+    Some(Ptr::new(RbNode {
+        color,
+        left,
+        key,
+        value,
+        right
+    }))
+}
+
+fn red<K, V>(t: &RbTree<K, V>) -> Option<&RbNode<K, V>>
+    where K: Ord + Clone + Debug, V: Clone + Debug {
+    match *t {
+        Some(ref n) if n.color == Color::Red => Some(n),
+        _ => None
+    }
+}
+
+fn black<K, V>(t: &RbTree<K, V>) -> Option<&RbNode<K, V>>
+    where K: Ord + Clone + Debug, V: Clone + Debug {
+    match *t {
+        Some(ref n) if n.color == Color::Black => Some(n),
+        _ => None
+    }
+}
+
+fn recolor<K, V>(n: &RbNode<K, V>, color: Color) -> RbTree<K, V>
+    where K: Ord + Clone + Debug, V: Clone + Debug {
+    node(color, n.left.clone(), n.key.clone(), n.value.clone(),
+         n.right.clone())
+}
+
+fn make_black<K, V>(t: RbTree<K, V>) -> RbTree<K, V>
+    where K: Ord + Clone + Debug, V: Clone + Debug {
+    match red(&t) {
+        Some(n) => recolor(n, Color::Black),
+        None => t
+    }
+}
+
+fn sub1<K, V>(t: &RbTree<K, V>) -> RbTree<K, V>
+    where K: Ord + Clone + Debug, V: Clone + Debug {
+    match black(t) {
+        Some(n) => recolor(n, Color::Red),
+        None => unreachable!("red-black invariant violation")
+    }
+}
+
+fn balance<K, V>(l: RbTree<K, V>, k: K, v: V, r: RbTree<K, V>)
+                 -> RbTree<K, V>
+    where K: Ord + Clone + Debug, V: Clone + Debug {
+    use self::Color::*;
+    if let (Some(a), Some(b)) = (red(&l), red(&r)) {
+        return node(Red, recolor(a, Black), k, v, recolor(b, Black));
+    }
+    if let Some(a) = red(&l) {
+        if let Some(b) = red(&a.left) {
+            return node(Red, recolor(b, Black), a.key.clone(), a.value.clone(),
+                        node(Black, a.right.clone(), k, v, r));
+        }
+        if let Some(b) = red(&a.right) {
+            return node(Red,
+                        node(Black, a.left.clone(), a.key.clone(),
+                             a.value.clone(), b.left.clone()),
+                        b.key.clone(), b.value.clone(),
+                        node(Black, b.right.clone(), k, v, r));
+        }
+    }
+    if let Some(a) = red(&r) {
+        if let Some(b) = red(&a.right) {
+            return node(Red, node(Black, l, k, v, a.left.clone()),
+                        a.key.clone(), a.value.clone(), recolor(b, Black));
+        }
+        if let Some(b) = red(&a.left) {
+            return node(Red, node(Black, l, k, v, b.left.clone()),
+                        b.key.clone(), b.value.clone(),
+                        node(Black, b.right.clone(), a.key.clone(),
+                             a.value.clone(), a.right.clone()));
+        }
+    }
+    node(Black, l, k, v, r)
+}
+
+fn rebuild<K, V>(color: Color, l: RbTree<K, V>, k: K, v: V, r: RbTree<K, V>)
+                 -> RbTree<K, V>
+    where K: Ord + Clone + Debug, V: Clone + Debug {
+    match color {
+        Color::Black => balance(l, k, v, r),
+        Color::Red => node(Color::Red, l, k, v, r)
+    }
+}
+
+fn ins<K, V>(t: &RbTree<K, V>, k: K, v: V) -> RbTree<K, V>
+    where K: Ord + Clone + Debug, V: Clone + Debug {
+    match *t {
+        None => node(Color::Red, None, k, v, None),
+        Some(ref n) => match k.cmp(&n.key) {
+            Ordering::Less =>
+                rebuild(n.color, ins(&n.left, k, v), n.key.clone(),
+                        n.value.clone(), n.right.clone()),
+            Ordering::Greater =>
+                rebuild(n.color, n.left.clone(), n.key.clone(),
+                        n.value.clone(), ins(&n.right, k, v)),
+            Ordering::Equal =>
+                node(n.color, n.left.clone(), k, v, n.right.clone())
+        }
+    }
+}
+
+// Deletion follows Kahrs, "Red-black trees with types".
+fn bal_left<K, V>(l: RbTree<K, V>, k: K, v: V, r: RbTree<K, V>)
+                  -> RbTree<K, V>
+    where K: Ord + Clone + Debug, V: Clone + Debug {
+    use self::Color::*;
+    if let Some(a) = red(&l) {
+        return node(Red, recolor(a, Black), k, v, r);
+    }
+    if let Some(b) = black(&r) {
+        return balance(l, k, v, recolor(b, Red));
+    }
+    if let Some(b) = red(&r) {
+        if let Some(c) = black(&b.left) {
+            return node(Red, node(Black, l, k, v, c.left.clone()),
+                        c.key.clone(), c.value.clone(),
+                        balance(c.right.clone(), b.key.clone(),
+                                b.value.clone(), sub1(&b.right)));
+        }
+    }
+    unreachable!("red-black invariant violation")
+}
+
+fn bal_right<K, V>(l: RbTree<K, V>, k: K, v: V, r: RbTree<K, V>)
+                   -> RbTree<K, V>
+    where K: Ord + Clone + Debug, V: Clone + Debug {
+    use self::Color::*;
+    if let Some(b) = red(&r) {
+        return node(Red, l, k, v, recolor(b, Black));
+    }
+    if let Some(a) = black(&l) {
+        return balance(recolor(a, Red), k, v, r);
+    }
+    if let Some(a) = red(&l) {
+        if let Some(c) = black(&a.right) {
+            return node(Red,
+                        balance(sub1(&a.left), a.key.clone(),
+                                a.value.clone(), c.left.clone()),
+                        c.key.clone(), c.value.clone(),
+                        node(Black, c.right.clone(), k, v, r));
+        }
+    }
+    unreachable!("red-black invariant violation")
+}
+
+fn app<K, V>(l: &RbTree<K, V>, r: &RbTree<K, V>) -> RbTree<K, V>
+    where K: Ord + Clone + Debug, V: Clone + Debug {
+    use self::Color::*;
+    let (a, b) = match (l, r) {
+        (&None, _) => return r.clone(),
+        (_, &None) => return l.clone(),
+        (Some(a), Some(b)) => (a, b)
+    };
+    match (a.color, b.color) {
+        (Red, Red) => {
+            let bc = app(&a.right, &b.left);
+            if let Some(m) = red(&bc) {
+                return node(Red,
+                            node(Red, a.left.clone(), a.key.clone(),
+                                 a.value.clone(), m.left.clone()),
+                            m.key.clone(), m.value.clone(),
+                            node(Red, m.right.clone(), b.key.clone(),
+                                 b.value.clone(), b.right.clone()));
+            }
+            node(Red, a.left.clone(), a.key.clone(), a.value.clone(),
+                 node(Red, bc, b.key.clone(), b.value.clone(),
+                      b.right.clone()))
+        }
+        (Black, Black) => {
+            let bc = app(&a.right, &b.left);
+            if let Some(m) = red(&bc) {
+                return node(Red,
+                            node(Black, a.left.clone(), a.key.clone(),
+                                 a.value.clone(), m.left.clone()),
+                            m.key.clone(), m.value.clone(),
+                            node(Black, m.right.clone(), b.key.clone(),
+                                 b.value.clone(), b.right.clone()));
+            }
+            bal_left(a.left.clone(), a.key.clone(), a.value.clone(),
+                     node(Black, bc, b.key.clone(), b.value.clone(),
+                          b.right.clone()))
+        }
+        (_, Red) => node(Red, app(l, &b.left), b.key.clone(),
+                         b.value.clone(), b.right.clone()),
+        (Red, _) => node(Red, a.left.clone(), a.key.clone(), a.value.clone(),
+                         app(&a.right, r))
+    }
+}
+
+fn del<K, V>(t: &RbTree<K, V>, k: &K) -> RbTree<K, V>
+    where K: Ord + Clone + Debug, V: Clone + Debug {
+    match *t {
+        None => None,
+        Some(ref n) => match k.cmp(&n.key) {
+            Ordering::Less => {
+                let l = del(&n.left, k);
+                if black(&n.left).is_some() {
+                    bal_left(l, n.key.clone(), n.value.clone(),
+                             n.right.clone())
+                } else {
+                    node(Color::Red, l, n.key.clone(), n.value.clone(),
+                         n.right.clone())
+                }
+            }
+            Ordering::Greater => {
+                let r = del(&n.right, k);
+                if black(&n.right).is_some() {
+                    bal_right(n.left.clone(), n.key.clone(), n.value.clone(),
+                              r)
+                } else {
+                    node(Color::Red, n.left.clone(), n.key.clone(),
+                         n.value.clone(), r)
+                }
+            }
+            Ordering::Equal => app(&n.left, &n.right)
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PfRbMap<K: Ord + Clone + Debug, V: Clone + Debug> {
+    len: usize,
+    root: RbTree<K, V>,
+}
+
+impl<K: Ord + Clone + Debug, V: Clone + Debug> PfRbMap<K, V> {
+    pub fn new() -> Self {
+        PfRbMap { len: 0, root: None }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn get(&self, k: &K) -> Option<&V> {
+        let mut t = &self.root;
+        while let Some(ref n) = *t {
+            match k.cmp(&n.key) {
+                Ordering::Less => t = &n.left,
+                Ordering::Greater => t = &n.right,
+                Ordering::Equal => return Some(&n.value)
+            }
+        }
+        None
+    }
+
+    pub fn contains_key(&self, k: &K) -> bool {
+        self.get(k).is_some()
+    }
+
+    pub fn insert(&self, k: K, v: V) -> Self {
+        let len = if self.contains_key(&k) { self.len } else { self.len + 1 };
+        PfRbMap { len, root: make_black(ins(&self.root, k, v)) }
+    }
+
+    pub fn delete(&self, k: &K) -> Self {
+        if !self.contains_key(k) {
+            return self.clone();
+        }
+        PfRbMap { len: self.len - 1, root: make_black(del(&self.root, k)) }
+    }
+
+    pub fn iter(&self) -> PfRbMapIter<'_, K, V> {
+        self.range(..)
+    }
+
+    pub fn range<R>(&self, range: R) -> PfRbMapIter<'_, K, V>
+        where R: RangeBounds<K> {
+        let mut it = PfRbMapIter {
+            stack: Vec::new(),
+            end: range.end_bound().cloned()
+        };
+        it.push_left(&self.root, range.start_bound());
+        it
+    }
+}
+
+impl<K: Ord + Clone + Debug, V: Clone + Debug> Default for PfRbMap<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct PfRbMapIter<'a, K: Ord + Clone + Debug, V: Clone + Debug> {
+    stack: Vec<&'a RbNode<K, V>>,
+    end: Bound<K>,
+}
+
+impl<'a, K: Ord + Clone + Debug, V: Clone + Debug> PfRbMapIter<'a, K, V> {
+    fn push_left(&mut self, mut t: &'a RbTree<K, V>, start: Bound<&K>) {
+        while let Some(ref n) = *t {
+            let after_start = match start {
+                Bound::Included(s) => n.key >= *s,
+                Bound::Excluded(s) => n.key > *s,
+                Bound::Unbounded => true
+            };
+            if after_start {
+                self.stack.push(n);
+                t = &n.left;
+            } else {
+                t = &n.right;
+            }
+        }
+    }
+}
+
+impl<'a, K: Ord + Clone + Debug, V: Clone + Debug> Iterator
+    for PfRbMapIter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<(&'a K, &'a V)> {
+        let n = self.stack.pop()?;
+        let before_end = match self.end {
+            Bound::Included(ref e) => n.key <= *e,
+            Bound::Excluded(ref e) => n.key < *e,
+            Bound::Unbounded => true
+        };
+        if !before_end {
+            self.stack.clear();
+            return None;
+        }
+        self.push_left(&n.right, Bound::Unbounded);
+        Some((&n.key, &n.value))
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct PfRbSet<T: Ord + Clone + Debug> {
+    map: PfRbMap<T, ()>
+}
+
+impl<T: Ord + Clone + Debug> PfRbSet<T> {
+    pub fn new() -> Self {
+        PfRbSet { map: PfRbMap::new() }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    pub fn member(&self, v: &T) -> bool {
+        self.map.contains_key(v)
+    }
+
+    pub fn insert(&self, v: T) -> Self {
+        if self.member(&v) {
+            return self.clone();
+        }
+        PfRbSet { map: self.map.insert(v, ()) }
+    }
+
+    pub fn delete(&self, v: &T) -> Self {
+        PfRbSet { map: self.map.delete(v) }
+    }
+
+    pub fn iter(&self) -> PfRbSetIter<'_, T> {
+        PfRbSetIter { it: self.map.iter() }
+    }
+
+    pub fn range<R>(&self, range: R) -> PfRbSetIter<'_, T>
+        where R: RangeBounds<T> {
+        PfRbSetIter { it: self.map.range(range) }
+    }
+}
+
+impl<T: Ord + Clone + Debug> Default for PfRbSet<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct PfRbSetIter<'a, T: Ord + Clone + Debug> {
+    it: PfRbMapIter<'a, T, ()>
+}
+
+impl<'a, T: Ord + Clone + Debug> Iterator for PfRbSetIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.it.next().map(|(k, _)| k)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    // Returns the black height, checking the invariants on the way.
+    fn check<K: Ord + Clone + Debug, V: Clone + Debug>(t: &RbTree<K, V>)
+                                                      -> usize {
+        match *t {
+            None => 1,
+            Some(ref n) => {
+                if n.color == Color::Red {
+                    assert!(red(&n.left).is_none());
+                    assert!(red(&n.right).is_none());
+                }
+                if let Some(ref l) = n.left {
+                    assert!(l.key < n.key);
+                }
+                if let Some(ref r) = n.right {
+                    assert!(n.key < r.key);
+                }
+                let lh = check(&n.left);
+                assert_eq!(lh, check(&n.right));
+                lh + if n.color == Color::Black { 1 } else { 0 }
+            }
+        }
+    }
+
+    #[test]
+    fn test_pf_rb_set() {
+        let s1 = PfRbSet::<i32>::new();
+        assert!(s1.is_empty());
+        let s2 = s1.insert(3).insert(1).insert(4).insert(1).insert(5);
+        assert_eq!(4, s2.len());
+        assert!(s2.member(&4));
+        assert!(!s2.member(&2));
+        let s3 = s2.delete(&4);
+        assert!(!s3.member(&4));
+        assert!(s2.member(&4));
+        assert_eq!(vec![1, 3, 5], s3.iter().cloned().collect::<Vec<i32>>());
+        assert_eq!(vec![1, 3, 4, 5], s2.iter().cloned().collect::<Vec<i32>>());
+        assert_eq!(vec![3, 4], s2.range(2..5).cloned().collect::<Vec<i32>>());
+        assert_eq!(vec![4, 5], s2.range(4..).cloned().collect::<Vec<i32>>());
+        assert_eq!(vec![1, 3], s2.range(..=3).cloned().collect::<Vec<i32>>());
+    }
+
+    #[test]
+    fn test_pf_rb_map() {
+        let mut m = PfRbMap::<i32, i32>::new();
+        let mut model = BTreeMap::new();
+        let mut versions = Vec::new();
+        let mut x: i32 = 1;
+        for i in 0..2000 {
+            x = (x * 1103 + 12345) % 1000;
+            if i % 3 == 2 {
+                m = m.delete(&x);
+                model.remove(&x);
+            } else {
+                m = m.insert(x, i);
+                model.insert(x, i);
+            }
+            check(&m.root);
+            assert!(red(&m.root).is_none());
+            assert_eq!(model.len(), m.len());
+            if i % 100 == 0 {
+                versions.push((m.clone(), model.clone()));
+            }
+        }
+        for (m, model) in versions {
+            assert_eq!(model.iter().collect::<Vec<_>>(),
+                       m.iter().collect::<Vec<_>>());
+            assert_eq!(model.range(100..200).collect::<Vec<_>>(),
+                       m.range(100..200).collect::<Vec<_>>());
+            for k in 0..1000 {
+                assert_eq!(model.get(&k), m.get(&k));
+            }
+        }
+    }
+
+    #[test]
+    fn test_pf_rb_map_sharing() {
+        let mut m = PfRbMap::<i32, i32>::new();
+        for i in 0..100 {
+            m = m.insert(i, i);
+        }
+        let m2 = m.insert(99, 0);
+        let (l1, l2) = match (&m.root, &m2.root) {
+            (Some(a), Some(b)) => (a.left.clone(), b.left.clone()),
+            _ => unreachable!()
+        };
+        assert!(Ptr::ptr_eq(&l1.unwrap(), &l2.unwrap()));
+        let m3 = m.delete(&1000);
+        assert!(Ptr::ptr_eq(m.root.as_ref().unwrap(),
+                           m3.root.as_ref().unwrap()));
+    }
+}
@@ -0,0 +1,309 @@
+use std::fmt::Debug;
+
+use lazy::Shareable;
+use lazy::Thunk;
+use list::PfList;
+use stream::Stream;
+use stream::StreamNode;
+
+fn mrg<T: Ord + Clone + Debug>(xs: &PfList<T>, ys: &PfList<T>) -> PfList<T> {
+    let mut v = Vec::new();
+    let mut xs = xs.pop().ok();
+    let mut ys = ys.pop().ok();
+    loop {
+        match (xs, ys) {
+            (Some((x, nxs)), Some((y, nys))) => {
+                if x <= y {
+                    v.push(x);
+                    xs = nxs.pop().ok();
+                    ys = Some((y, nys));
+                } else {
+                    v.push(y);
+                    xs = Some((x, nxs));
+                    ys = nys.pop().ok();
+                }
+            }
+            (Some((x, rest)), None) | (None, Some((x, rest))) => {
+                let mut l = rest.push(x);
+                while let Some(x) = v.pop() {
+                    l = l.push(x);
+                }
+                return l;
+            }
+            (None, None) => {
+                let mut l = PfList::new();
+                while let Some(x) = v.pop() {
+                    l = l.push(x);
+                }
+                return l;
+            }
+        }
+    }
+}
+
+// 6.4.3 Bottom-up mergesort with sharing
+#[derive(Clone, Debug)]
+pub struct PfSortable<'a, T: 'a + Ord + Clone + Debug> {
+    size: usize,
+    segs: Thunk<'a, PfList<PfList<T>>>,
+}
+
+impl<'a, T: 'a + Ord + Clone + Debug + Shareable> PfSortable<'a, T> {
+    fn add_seg(seg: PfList<T>, segs: PfList<PfList<T>>, size: usize)
+               -> PfList<PfList<T>> {
+        let mut seg = seg;
+        let mut segs = segs;
+        let mut size = size;
+        while size % 2 == 1 {
+            let (s, rest) = segs.pop().unwrap();
+            seg = mrg(&seg, &s);
+            segs = rest;
+            size /= 2;
+        }
+        segs.push(seg)
+    }
+
+    pub fn new() -> Self {
+        PfSortable { size: 0, segs: lazy!(PfList::new()) }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    pub fn add(&self, v: T) -> Self {
+        let size = self.size;
+        let segs = self.segs.clone();
+        PfSortable {
+            size: size + 1,
+            segs: lazy!(Self::add_seg(PfList::new().push(v),
+                                      segs.eval(), size))
+        }
+    }
+
+    pub fn sort(&self) -> PfList<T> {
+        let mut xs = PfList::new();
+        for seg in self.segs.force().iter() {
+            xs = mrg(&xs, seg);
+        }
+        xs
+    }
+}
+
+impl<'a, T: 'a + Ord + Clone + Debug + Shareable> Default
+    for PfSortable<'a, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn mrg_stream<'a, T: 'a + Ord + Clone + Debug + Shareable>(xs: &Stream<'a, T>,
+                                              ys: &Stream<'a, T>)
+                                              -> Stream<'a, T> {
+    let xs = xs.clone();
+    let ys = ys.clone();
+    Stream::new(lazy!(
+        match (xs.force(), ys.force()) {
+            (StreamNode::Nil, n) | (n, StreamNode::Nil) => n.clone(),
+            (StreamNode::Cons(x, xt), StreamNode::Cons(y, yt)) => {
+                if x <= y {
+                    let (xt, ys) = (Stream::new(xt.clone()), ys.clone());
+                    StreamNode::Cons(x.clone(),
+                                     lazy!(mrg_stream(&xt, &ys).eval()))
+                } else {
+                    let (xs, yt) = (xs.clone(), Stream::new(yt.clone()));
+                    StreamNode::Cons(y.clone(),
+                                     lazy!(mrg_stream(&xs, &yt).eval()))
+                }
+            }
+        }
+    ))
+}
+
+// 7.4 Bottom-up mergesort with scheduling
+type Schedule<'a, T> = PfList<Stream<'a, T>>;
+type ScheduledSegments<'a, T> = PfList<(Stream<'a, T>, Schedule<'a, T>)>;
+
+#[derive(Clone, Debug)]
+pub struct PfScheduledSortable<'a, T: 'a + Ord + Clone + Debug> {
+    size: usize,
+    segs: ScheduledSegments<'a, T>,
+}
+
+impl<'a, T: 'a + Ord + Clone + Debug + Shareable> PfScheduledSortable<'a, T> {
+    fn exec1(sched: Schedule<'a, T>) -> Schedule<'a, T> {
+        let mut sched = sched;
+        while let Ok((s, rest)) = sched.pop() {
+            match s.tail() {
+                Ok(t) => return rest.push(t),
+                Err(_) => sched = rest
+            }
+        }
+        sched
+    }
+
+    fn exec2(segs: ScheduledSegments<'a, T>) -> ScheduledSegments<'a, T> {
+        match segs.pop() {
+            Ok(((xs, sched), rest)) => Self::exec2(rest)
+                .push((xs, Self::exec1(Self::exec1(sched)))),
+            Err(_) => segs
+        }
+    }
+
+    pub fn new() -> Self {
+        PfScheduledSortable { size: 0, segs: PfList::new() }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    pub fn add(&self, v: T) -> Self {
+        let mut xs = Stream::empty().push(v);
+        let mut segs = self.segs.clone();
+        let mut size = self.size;
+        let mut rsched = PfList::new();
+        while size % 2 == 1 {
+            let ((ys, _), rest) = segs.pop().unwrap();
+            xs = mrg_stream(&xs, &ys);
+            rsched = rsched.push(xs.clone());
+            segs = rest;
+            size /= 2;
+        }
+        PfScheduledSortable {
+            size: self.size + 1,
+            segs: Self::exec2(segs.push((xs, rsched.rev())))
+        }
+    }
+
+    pub fn sort(&self) -> PfList<T> {
+        let mut xs = Stream::empty();
+        for (seg, _) in self.segs.iter() {
+            xs = mrg_stream(&xs, seg);
+        }
+        let mut l = PfList::new();
+        for x in xs.iter().collect::<Vec<T>>().into_iter().rev() {
+            l = l.push(x);
+        }
+        l
+    }
+}
+
+impl<'a, T: 'a + Ord + Clone + Debug + Shareable> Default
+    for PfScheduledSortable<'a, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cmp::Ordering;
+    use std::sync::atomic;
+    use std::sync::atomic::AtomicUsize;
+
+    static COMPARISONS: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    struct Counted(i32);
+
+    impl Ord for Counted {
+        fn cmp(&self, other: &Self) -> Ordering {
+            COMPARISONS.fetch_add(1, atomic::Ordering::SeqCst);
+            self.0.cmp(&other.0)
+        }
+    }
+
+    impl PartialOrd for Counted {
+        fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    fn to_vec<T: Clone + Debug>(l: &PfList<T>) -> Vec<T> {
+        let mut v = Vec::new();
+        let mut l = l.clone();
+        while let Ok((x, nl)) = l.pop() {
+            v.push(x);
+            l = nl;
+        }
+        v
+    }
+
+    fn test_sortable<S, A, F>(s: S, add: A, sort: F)
+        where S: Clone, A: Fn(&S, i32) -> S, F: Fn(&S) -> PfList<i32> {
+        assert!(to_vec(&sort(&s)).is_empty());
+
+        let mut s = s;
+        let mut model = Vec::new();
+        let mut snapshots = Vec::new();
+        let mut x: i32 = 1;
+        for _ in 0..300 {
+            x = (x * 1103 + 12345) % 1000;
+            s = add(&s, x);
+            model.push(x);
+            model.sort();
+            assert_eq!(model, to_vec(&sort(&s)));
+            snapshots.push((s.clone(), model.clone()));
+        }
+        for (s, model) in snapshots.into_iter().rev().step_by(7) {
+            let t = add(&s, -1);
+            assert_eq!(model, to_vec(&sort(&s)));
+            let mut expected = model.clone();
+            expected.insert(0, -1);
+            assert_eq!(expected, to_vec(&sort(&t)));
+        }
+    }
+
+    #[test]
+    fn test_pf_sortable() {
+        let s = PfSortable::<i32>::new();
+        assert!(s.is_empty());
+        assert_eq!(3, s.add(1).add(2).add(3).len());
+        test_sortable(s, |s, x| s.add(x), |s| s.sort());
+    }
+
+    #[test]
+    fn test_pf_scheduled_sortable() {
+        let s = PfScheduledSortable::<i32>::new();
+        assert!(s.is_empty());
+        assert_eq!(3, s.add(1).add(2).add(3).len());
+        test_sortable(s, |s, x| s.add(x), |s| s.sort());
+    }
+
+    #[test]
+    fn test_pf_scheduled_sortable_forces_segments() {
+        let mut s = PfScheduledSortable::new();
+        let mut x: i32 = 1;
+        let mut checked = 0;
+        for _ in 0..300 {
+            x = (x * 1103 + 12345) % 1000;
+            s = s.add(Counted(x));
+            // A segment whose schedule has run out must be fully forced,
+            // so walking it makes no further comparisons.
+            let mut segs = s.segs.clone();
+            while let Ok(((seg, sched), rest)) = segs.pop() {
+                if sched.is_empty() {
+                    let before = COMPARISONS.load(atomic::Ordering::SeqCst);
+                    let len = seg.iter().count();
+                    assert_eq!(before,
+                               COMPARISONS.load(atomic::Ordering::SeqCst));
+                    if len > 1 {
+                        checked += 1;
+                    }
+                }
+                segs = rest;
+            }
+        }
+        assert!(checked > 0);
+    }
+}
@@ -2,6 +2,7 @@
 
 use std::fmt::Debug;
 
+use lazy::Shareable;
 use lazy::Thunk;
 
 #[derive(Clone, Debug)]
@@ -15,24 +16,27 @@ pub struct Stream<'a, T: 'a + Clone + Debug> {
     head: Thunk<'a, StreamNode<'a, T>>
 }
 
-impl<'a, T: 'a + Clone + Debug> Iterator for Stream<'a, T> {
+impl<'a, T: 'a + Clone + Debug + Shareable> Iterator for Stream<'a, T> {
     type Item = T;
     fn next(&mut self) -> Option<T> {
-        match self.eval() {
-            StreamNode::Nil => None,
-            StreamNode::Cons(v, t) => {
-                self.head = t.clone();
-                Some(v.clone())
-            }
-        }
+        let (v, t) = match *self.force() {
+            StreamNode::Nil => return None,
+            StreamNode::Cons(ref v, ref t) => (v.clone(), t.clone())
+        };
+        self.head = t;
+        Some(v)
     }
 }
 
-impl<'a, T: 'a + Clone + Debug> Stream<'a, T> {
+impl<'a, T: 'a + Clone + Debug + Shareable> Stream<'a, T> {
     pub fn new(t: Thunk<'a, StreamNode<'a, T>>) -> Self {
         Stream { head: t }
     }
 
+    pub fn force(&self) -> &StreamNode<'a, T> {
+        self.head.force()
+    }
+
     pub fn eval(&self) -> StreamNode<'a, T> {
         self.head.eval()
     }
@@ -43,9 +47,7 @@ impl<'a, T: 'a + Clone + Debug> Stream<'a, T> {
 
     pub fn push(&self, v: T) -> Self {
         let tail = self.head.clone();
-        Stream::new(lazy!(
-            StreamNode::Cons(v.clone(), tail.clone())
-        ))
+        Stream::new(lazy!(StreamNode::Cons(v, tail)))
     }
 
     pub fn make<I>(it: I) -> Self
@@ -62,33 +64,41 @@ impl<'a, T: 'a + Clone + Debug> Stream<'a, T> {
     }
 
     pub fn head(&self) -> Result<T, &str> {
-        match self.eval() {
+        self.head_ref().cloned()
+    }
+
+    // Borrows the first element instead of cloning it.
+    pub fn head_ref(&self) -> Result<&T, &str> {
+        match *self.force() {
             StreamNode::Nil => Err("head for empty stream"),
-            StreamNode::Cons(v, _) => Ok(v),
+            StreamNode::Cons(ref v, _) => Ok(v),
         }
     }
 
     pub fn tail(&self) -> Result<Self, &str> {
-        match self.eval() {
+        match *self.force() {
             StreamNode::Nil => Err("tail for empty stream"),
-            StreamNode::Cons(_, t) => Ok(Stream::new(t)),
+            StreamNode::Cons(_, ref t) => Ok(Stream::new(t.clone())),
         }
     }
 
     pub fn pop(&self) -> Result<(T, Self), &str> {
-        match self.eval() {
+        match *self.force() {
             StreamNode::Nil => Err("pop for empty stream"),
-            StreamNode::Cons(v, t) => Ok((v, Stream::new(t))),
+            StreamNode::Cons(ref v, ref t) =>
+                Ok((v.clone(), Stream::new(t.clone()))),
         }
     }
 
     pub fn concat(&self, t: Self) -> Self {
-        match self.eval() {
+        match *self.force() {
             StreamNode::Nil => t,
-            StreamNode::Cons(v, s) => Stream::new(lazy!(
-                StreamNode::Cons(
-                    v.clone(), Stream::new(s.clone()).concat(t.clone()).head)
-            ))
+            StreamNode::Cons(ref v, ref s) => {
+                let (v, s) = (v.clone(), s.clone());
+                Stream::new(lazy!(
+                    StreamNode::Cons(v, Stream::new(s).concat(t).head)
+                ))
+            }
         }
     }
 
@@ -96,23 +106,40 @@ impl<'a, T: 'a + Clone + Debug> Stream<'a, T> {
         if n == 0 {
             return Self::empty();
         }
-        match self.eval() {
+        match *self.force() {
             StreamNode::Nil => Self::empty(),
-            StreamNode::Cons(v, s) => Stream::new(lazy!(
-                StreamNode::Cons(v.clone(),
-                                 Stream::new(s.clone()).take_n(n-1).head)
-            ))
+            StreamNode::Cons(ref v, ref s) => {
+                let (v, s) = (v.clone(), s.clone());
+                Stream::new(lazy!(
+                    StreamNode::Cons(v, Stream::new(s).take_n(n-1).head)
+                ))
+            }
         }
     }
 
+    pub fn drop_n(&self, n: usize) -> Self {
+        let s = self.clone();
+        Stream::new(lazy!({
+            let mut s = s;
+            for _ in 0..n {
+                match s.tail() {
+                    Ok(t) => s = t,
+                    Err(_) => break
+                }
+            }
+            s.eval()
+        }))
+    }
+
     fn reverse_impl(s: Thunk<'a, StreamNode<'a, T>>,
                     r: Thunk<'a, StreamNode<'a, T>>)
                     -> Thunk<'a, StreamNode<'a, T>> {
-        match s.eval() {
+        match *s.force() {
             StreamNode::Nil => r,
-            StreamNode::Cons(v, s) => {
-                let c = lazy!(StreamNode::Cons(v.clone(), r.clone()));
-                lazy!(Self::reverse_impl(s.clone(), c.clone()).eval())
+            StreamNode::Cons(ref v, ref s) => {
+                let (v, s) = (v.clone(), s.clone());
+                let c = lazy!(StreamNode::Cons(v, r));
+                lazy!(Self::reverse_impl(s, c).eval())
             }
         }
     }
@@ -126,6 +153,19 @@ impl<'a, T: 'a + Clone + Debug> Stream<'a, T> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::Arc;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+
+    #[derive(Debug)]
+    struct Counted(Arc<AtomicUsize>);
+
+    impl Clone for Counted {
+        fn clone(&self) -> Self {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Counted(self.0.clone())
+        }
+    }
 
     #[test]
     fn test_stream() {
@@ -142,4 +182,51 @@ mod tests {
         let s = Stream::make(1..4).concat(Stream::make(5..10));
         assert_eq!(vec![1,2,3,5,6], s.take_n(5).iter().collect::<Vec<i32>>());
     }
+
+    #[test]
+    fn test_stream_drop() {
+        let s = Stream::make(1..4).concat(Stream::make(5..10));
+        assert_eq!(vec![7,8,9], s.drop_n(5).iter().collect::<Vec<i32>>());
+        assert!(s.drop_n(10).head().is_err());
+
+        // Dropping is suspended until the result is forced.
+        let forced = Arc::new(AtomicUsize::new(0));
+        let f = forced.clone();
+        let s = Stream::new(lazy!({
+            f.fetch_add(1, Ordering::SeqCst);
+            Stream::make(1..4).eval()
+        }));
+        let d = s.drop_n(2);
+        assert_eq!(0, forced.load(Ordering::SeqCst));
+        assert_eq!(3, d.head().unwrap());
+        assert_eq!(1, forced.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_stream_borrows() {
+        let clones = Arc::new(AtomicUsize::new(0));
+        let s = Stream::make((0..3).map(|_| Counted(clones.clone())));
+        let walk = |s: &Stream<Counted>| {
+            let mut t = s.clone();
+            while let Ok(nt) = t.tail() {
+                t = nt;
+            }
+        };
+        // Payloads are moved into the stream and borrowed when walking it.
+        walk(&s);
+        walk(&s);
+        assert_eq!(0, clones.load(Ordering::SeqCst));
+        assert!(s.head().is_ok());
+        assert_eq!(1, clones.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_stream_head_ref() {
+        let clones = Arc::new(AtomicUsize::new(0));
+        let s = Stream::make((0..3).map(|_| Counted(clones.clone())));
+        assert!(s.head_ref().is_ok());
+        assert!(std::ptr::eq(s.head_ref().unwrap(), s.head_ref().unwrap()));
+        assert_eq!(0, clones.load(Ordering::SeqCst));
+        assert!(Stream::<i32>::empty().head_ref().is_err());
+    }
 }
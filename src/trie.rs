@@ -0,0 +1,216 @@
+// 10.3.1 Trie
+use std::fmt::Debug;
+
+use Ptr;
+use rbtree::PfRbMap;
+use rbtree::PfRbMapIter;
+
+#[derive(Clone, Debug)]
+pub struct PfTrie<K: Ord + Clone + Debug, V: Clone + Debug> {
+    value: Option<V>,
+    children: PfRbMap<K, Ptr<PfTrie<K, V>>>,
+}
+
+impl<K: Ord + Clone + Debug, V: Clone + Debug> PfTrie<K, V> {
+    fn find(&self, key: &[K]) -> Option<&Self> {
+        let mut t = self;
+        for k in key {
+            t = t.children.get(k)?;
+        }
+        Some(t)
+    }
+
+    pub fn new() -> Self {
+        PfTrie { value: None, children: PfRbMap::new() }
+    }
+
+    // Empty subtries are pruned on removal, so only the root is checked.
+    pub fn is_empty(&self) -> bool {
+        self.value.is_none() && self.children.is_empty()
+    }
+
+    pub fn lookup(&self, key: &[K]) -> Option<&V> {
+        self.find(key)?.value.as_ref()
+    }
+
+    pub fn contains_key(&self, key: &[K]) -> bool {
+        self.lookup(key).is_some()
+    }
+
+    pub fn insert(&self, key: &[K], v: V) -> Self {
+        match key.split_first() {
+            None => PfTrie {
+                value: Some(v),
+                children: self.children.clone()
+            },
+            Some((k, ks)) => {
+                let t = match self.children.get(k) {
+                    Some(t) => t.insert(ks, v),
+                    None => PfTrie::new().insert(ks, v)
+                };
+                PfTrie {
+                    value: self.value.clone(),
+                    children: self.children.insert(k.clone(), Ptr::new(t))
+                }
+            }
+        }
+    }
+
+    pub fn remove(&self, key: &[K]) -> Self {
+        self.delete(key).unwrap_or_else(|| self.clone())
+    }
+
+    // None when `key` has no binding, so that the caller keeps sharing
+    // the subtrie it already has instead of rebuilding the path.
+    fn delete(&self, key: &[K]) -> Option<Self> {
+        match key.split_first() {
+            None => {
+                self.value.as_ref()?;
+                Some(PfTrie { value: None, children: self.children.clone() })
+            }
+            Some((k, ks)) => {
+                let t = self.children.get(k)?.delete(ks)?;
+                let children = if t.is_empty() {
+                    self.children.delete(k)
+                } else {
+                    self.children.insert(k.clone(), Ptr::new(t))
+                };
+                Some(PfTrie { value: self.value.clone(), children })
+            }
+        }
+    }
+
+    pub fn iter(&self) -> PfTrieIter<'_, K, V> {
+        self.iter_prefix(&[])
+    }
+
+    // Bindings whose keys start with `prefix`, in key order.
+    pub fn iter_prefix(&self, prefix: &[K]) -> PfTrieIter<'_, K, V> {
+        PfTrieIter {
+            stack: Vec::new(),
+            next: self.find(prefix).map(|t| (prefix.to_vec(), t))
+        }
+    }
+}
+
+impl<K: Ord + Clone + Debug, V: Clone + Debug> Default for PfTrie<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+type TrieFrame<'a, K, V> = (Vec<K>, PfRbMapIter<'a, K, Ptr<PfTrie<K, V>>>);
+
+pub struct PfTrieIter<'a, K: Ord + Clone + Debug, V: Clone + Debug> {
+    stack: Vec<TrieFrame<'a, K, V>>,
+    next: Option<(Vec<K>, &'a PfTrie<K, V>)>,
+}
+
+impl<'a, K: Ord + Clone + Debug, V: Clone + Debug> Iterator
+    for PfTrieIter<'a, K, V> {
+    type Item = (Vec<K>, &'a V);
+
+    fn next(&mut self) -> Option<(Vec<K>, &'a V)> {
+        loop {
+            if let Some((key, t)) = self.next.take() {
+                self.stack.push((key.clone(), t.children.iter()));
+                if let Some(ref v) = t.value {
+                    return Some((key, v));
+                }
+                continue;
+            }
+            let child = {
+                let &mut (ref key, ref mut children) = self.stack.last_mut()?;
+                children.next().map(|(k, t)| {
+                    let mut key = key.clone();
+                    key.push(k.clone());
+                    (key, &**t)
+                })
+            };
+            match child {
+                Some(c) => self.next = Some(c),
+                None => {
+                    self.stack.pop();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_pf_trie() {
+        let t1 = PfTrie::<u8, i32>::new();
+        assert!(t1.is_empty());
+        let t2 = t1.insert(b"tea", 1).insert(b"ten", 2).insert(b"to", 3)
+            .insert(b"", 4).insert(b"tea", 5);
+        assert!(!t2.is_empty());
+        assert_eq!(Some(&5), t2.lookup(b"tea"));
+        assert_eq!(Some(&4), t2.lookup(b""));
+        assert_eq!(None, t2.lookup(b"te"));
+        assert_eq!(None, t2.lookup(b"tent"));
+        assert!(t1.lookup(b"tea").is_none());
+
+        let words = |it: PfTrieIter<u8, i32>| -> Vec<(String, i32)> {
+            it.map(|(k, v)| (String::from_utf8(k).unwrap(), *v)).collect()
+        };
+        assert_eq!(vec![("tea".to_string(), 5), ("ten".to_string(), 2)],
+                   words(t2.iter_prefix(b"te")));
+        assert!(words(t2.iter_prefix(b"x")).is_empty());
+        assert_eq!(4, words(t2.iter()).len());
+
+        let t3 = t2.remove(b"ten").remove(b"to").remove(b"nope");
+        assert_eq!(vec![("".to_string(), 4), ("tea".to_string(), 5)],
+                   words(t3.iter()));
+        assert!(t2.contains_key(b"ten"));
+        let t4 = t3.remove(b"").remove(b"tea");
+        assert!(t4.is_empty());
+    }
+
+    #[test]
+    fn test_pf_trie_remove_missing() {
+        let t1 = PfTrie::<u8, i32>::new().insert(b"tea", 1).insert(b"to", 2);
+        let t2 = t1.remove(b"ten").remove(b"te").remove(b"x");
+        assert!(Ptr::ptr_eq(t1.children.get(&b't').unwrap(),
+                            t2.children.get(&b't').unwrap()));
+        let t3 = t1.remove(b"to");
+        assert!(!Ptr::ptr_eq(t1.children.get(&b't').unwrap(),
+                             t3.children.get(&b't').unwrap()));
+    }
+
+    #[test]
+    fn test_pf_trie_model() {
+        let mut t = PfTrie::<u8, i32>::new();
+        let mut model = BTreeMap::new();
+        let mut x: i32 = 1;
+        for i in 0..2000 {
+            x = (x * 1103 + 12345) % 10007;
+            let key: Vec<u8> = (0..x % 4).map(|j| b'a' + ((x >> j) % 3) as u8)
+                .collect();
+            if i % 3 == 2 {
+                t = t.remove(&key);
+                model.remove(&key);
+            } else {
+                t = t.insert(&key, i);
+                model.insert(key.clone(), i);
+            }
+            assert_eq!(model.get(&key), t.lookup(&key));
+            assert_eq!(model.is_empty(), t.is_empty());
+        }
+        let pairs: Vec<(Vec<u8>, i32)> =
+            t.iter().map(|(k, v)| (k, *v)).collect();
+        let expected: Vec<(Vec<u8>, i32)> =
+            model.iter().map(|(k, v)| (k.clone(), *v)).collect();
+        assert_eq!(expected, pairs);
+        let prefixed: Vec<(Vec<u8>, i32)> =
+            t.iter_prefix(b"ab").map(|(k, v)| (k, *v)).collect();
+        let expected: Vec<(Vec<u8>, i32)> = model.iter()
+            .filter(|&(k, _)| k.starts_with(b"ab"))
+            .map(|(k, v)| (k.clone(), *v)).collect();
+        assert_eq!(expected, prefixed);
+    }
+}
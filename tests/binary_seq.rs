@@ -0,0 +1,22 @@
+extern crate pfds;
+
+use pfds::list::PfBinarySeq;
+
+// Instantiated from a dependent crate, where none of pfds' crate-level
+// attributes apply.
+#[test]
+fn test_binary_seq_downstream() {
+    let mut s: PfBinarySeq<i32> = PfBinarySeq::new();
+    assert!(s.is_empty());
+    for i in (0..1000).rev() {
+        s = s.cons(i);
+    }
+    assert_eq!(1000, s.size());
+    assert_eq!(Ok(500), s.lookup(500));
+    let u = s.update(500, -1).unwrap();
+    assert_eq!(Ok(-1), u.lookup(500));
+    assert_eq!(Ok(500), s.lookup(500));
+    let (v, t) = u.uncons().unwrap();
+    assert_eq!(0, v);
+    assert_eq!(999, t.size());
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "sync")]
+
+extern crate pfds;
+
+use pfds::catenable::*;
+use pfds::heap::*;
+use pfds::list::*;
+use pfds::queue::*;
+use pfds::rbtree::*;
+use pfds::sortable::*;
+use pfds::stream::*;
+use pfds::trie::*;
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+// Checked from a dependent crate, so that no crate-level attribute of
+// pfds can hide an overflow while proving the bounds.
+#[test]
+fn test_send_sync() {
+    assert_send_sync::<Stream<i32>>();
+
+    assert_send_sync::<PfList<i32>>();
+    assert_send_sync::<PfRandomAccessList<i32>>();
+    assert_send_sync::<PfSkewList<i32>>();
+    assert_send_sync::<PfBinarySeq<i32>>();
+
+    assert_send_sync::<PfBatchedQueue<i32>>();
+    assert_send_sync::<PfBankerQueue<i32>>();
+    assert_send_sync::<PfPhysicistQueue<i32>>();
+    assert_send_sync::<PfRealTimeQueue<i32>>();
+    assert_send_sync::<PfHoodMelvilleQueue<i32>>();
+    assert_send_sync::<PfBankerDeque<i32>>();
+    assert_send_sync::<PfRealTimeDeque<i32>>();
+
+    assert_send_sync::<PfLeftistHeap<i32>>();
+    assert_send_sync::<PfBinomialHeap<i32>>();
+    assert_send_sync::<PfSplayHeap<i32>>();
+    assert_send_sync::<PfPairingHeap<i32>>();
+    assert_send_sync::<PfLazyPairingHeap<i32>>();
+    assert_send_sync::<PfScheduledBinomialHeap<i32>>();
+    assert_send_sync::<PfSkewBinomialHeap<i32>>();
+    assert_send_sync::<PfBootstrappedHeap<PfSkewBinomialHeap<i32>>>();
+
+    assert_send_sync::<PfRbMap<i32, i32>>();
+    assert_send_sync::<PfRbSet<i32>>();
+    assert_send_sync::<PfTrie<u8, i32>>();
+
+    assert_send_sync::<PfCatenableList<i32>>();
+    assert_send_sync::<PfSimpleCatenableDeque<i32>>();
+    assert_send_sync::<PfCatenableDeque<i32>>();
+
+    assert_send_sync::<PfSortable<i32>>();
+    assert_send_sync::<PfScheduledSortable<i32>>();
+}